@@ -0,0 +1,27 @@
+//! Matches an arbitrary section glob against an arbitrary path (separated by
+//! the first newline).
+
+#![no_main]
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use editorconfig_core::{Options, properties_from_source};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    let (pattern, path) = data.split_once('\n').unwrap_or((data, "file"));
+    if pattern.contains(['\n', '\r']) || path.contains(['\n', '\r', '\0']) {
+        return;
+    }
+
+    let configs = HashMap::from([(
+        PathBuf::from("/repo/.editorconfig"),
+        format!("[{pattern}]\nkey = value\n"),
+    )]);
+    let _ = properties_from_source(
+        format!("/repo/{path}"),
+        Options::default(),
+        &configs,
+    );
+});
@@ -0,0 +1,28 @@
+//! Feeds arbitrary bytes to the parser, as an EditorConfig file, and resolves,
+//! lints and formats it.
+
+#![no_main]
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use editorconfig_core::{
+    Document, FormatOptions, Options, format, lint, properties_from_source,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let contents = String::from_utf8_lossy(data);
+
+    let document = Document::parse(&contents);
+    let _ = lint(&document);
+    let _ = format(&contents, FormatOptions::default());
+
+    let configs = HashMap::from([(
+        PathBuf::from("/repo/.editorconfig"),
+        contents.into_owned(),
+    )]);
+    for path in ["/repo/main.rs", "/repo/src/lib.rs", "/repo/1/2.c"] {
+        let _ = properties_from_source(path, Options::default(), &configs);
+    }
+});
@@ -0,0 +1,130 @@
+#ifndef EDITORCONFIG_H
+#define EDITORCONFIG_H
+
+/* Generated by cbindgen from `src/capi.rs`; do not edit. */
+
+// An EditorConfig file couldn't be read (or the path isn't valid unicode).
+#define EDITORCONFIG_PARSE_IO_ERROR -1
+
+// The path of the file to parse is not a full path.
+#define EDITORCONFIG_PARSE_NOT_FULL_PATH -2
+
+// A memory error occurred.
+#define EDITORCONFIG_PARSE_MEMORY_ERROR -3
+
+// The version set in the handle is greater than the current version.
+#define EDITORCONFIG_PARSE_VERSION_TOO_NEW -4
+
+// The state of a parse: its options, and then its results.
+//
+// Created by [`editorconfig_handle_init`], and destroyed by
+// [`editorconfig_handle_destroy`].
+typedef struct editorconfig_handle_s editorconfig_handle_s;
+
+// A pointer to a [`Handle`], as named by `editorconfig.h`.
+typedef struct editorconfig_handle_s *editorconfig_handle;
+
+
+
+// Creates a handle (which must be destroyed with
+// [`editorconfig_handle_destroy`]).
+editorconfig_handle editorconfig_handle_init(void);
+
+// Destroys the handle `h`, returning `0` (or `-1` if `h` is null).
+//
+// # Safety
+//
+// `h` must be null or a handle from [`editorconfig_handle_init`], which
+// wasn't destroyed yet. The strings obtained through it become dangling.
+int editorconfig_handle_destroy(editorconfig_handle h);
+
+// The name of the EditorConfig files, or null if it wasn't set (i.e.,
+// `.editorconfig`).
+//
+// # Safety
+//
+// `h` must be a live handle. The string lives until the name is set again,
+// or the handle is destroyed.
+const char *editorconfig_handle_get_conf_file_name(editorconfig_handle h);
+
+// Sets the name of the EditorConfig files (null for `.editorconfig`).
+//
+// # Safety
+//
+// `h` must be a live handle, and `conf_file_name` must be null or a
+// NUL-terminated string.
+void editorconfig_handle_set_conf_file_name(editorconfig_handle h, const char *conf_file_name);
+
+// Gets the version set in the handle (`0.0.0` if it wasn't set).
+//
+// # Safety
+//
+// `h` must be a live handle, and each of `major`, `minor` and `patch` must be
+// null or valid for writes.
+void editorconfig_handle_get_version(editorconfig_handle h, int *major, int *minor, int *patch);
+
+// Sets the version to use (the current version is used if it's `0.0.0`, or
+// isn't set).
+//
+// # Safety
+//
+// `h` must be a live handle.
+void editorconfig_handle_set_version(editorconfig_handle h, int major, int minor, int patch);
+
+// The EditorConfig file in which the last parse error occurred, or null.
+//
+// # Safety
+//
+// `h` must be a live handle. The string lives until the next parse, or until
+// the handle is destroyed.
+const char *editorconfig_handle_get_err_file(editorconfig_handle h);
+
+// The number of properties the last parse found.
+//
+// # Safety
+//
+// `h` must be a live handle.
+int editorconfig_handle_get_name_value_count(editorconfig_handle h);
+
+// Gets the name and the value of the `n`th property (sorted by name) the last
+// parse found. Both are set to null if there's no such property.
+//
+// # Safety
+//
+// `h` must be a live handle, and `name` and `value` must be null or valid for
+// writes. The strings live until the next parse, or until the handle is
+// destroyed.
+void editorconfig_handle_get_name_value(editorconfig_handle h,
+                                        int n,
+                                        const char **name,
+                                        const char **value);
+
+// Resolves the properties of the file at `full_filename` (a full path) into
+// `h`.
+//
+// Returns `0` on success, the line number of the parse error (see
+// [`editorconfig_handle_get_err_file`]) if an EditorConfig file couldn't be
+// parsed (i.e., it has a malformed line, or a section glob which can't be
+// compiled), or one of the negative `EDITORCONFIG_PARSE_*` errors.
+//
+// # Safety
+//
+// `full_filename` must be a NUL-terminated string, and `h` must be a live
+// handle. The strings previously obtained through `h` become dangling.
+int editorconfig_parse(const char *full_filename, editorconfig_handle h);
+
+// A message describing the error `err_num`, returned by
+// [`editorconfig_parse`].
+const char *editorconfig_get_error_msg(int err_num);
+
+// Gets the version of the library (i.e., [`MAX_VERSION`]).
+//
+// # Safety
+//
+// Each of `major`, `minor` and `patch` must be null or valid for writes.
+void editorconfig_get_version(int *major, int *minor, int *patch);
+
+// The suffix of the version of the library (e.g., `-beta`), which is empty.
+const char *editorconfig_get_version_suffix(void);
+
+#endif  /* EDITORCONFIG_H */
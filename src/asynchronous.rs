@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::path::Path;
+
+use crate::{
+    Behavior, Document, Error, Options, Properties, ancestors, resolve,
+};
+
+/// A filesystem from which EditorConfig files are read without blocking.
+pub trait AsyncFileSystem {
+    /// Reads the file at `path` into a string (with an error of kind
+    /// [`NotFound`](io::ErrorKind::NotFound) if it doesn't exist).
+    fn read_to_string(
+        &self,
+        path: &Path,
+    ) -> impl Future<Output = io::Result<String>> + Send;
+}
+
+/// The filesystem, through [`tokio::fs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TokioFileSystem;
+
+impl AsyncFileSystem for TokioFileSystem {
+    fn read_to_string(
+        &self,
+        path: &Path,
+    ) -> impl Future<Output = io::Result<String>> + Send {
+        tokio::fs::read_to_string(path.to_path_buf())
+    }
+}
+
+/// Like [`properties`](crate::properties), but reads the EditorConfig files
+/// without blocking.
+pub async fn properties_async<P>(path: P) -> Result<Properties, Error>
+where
+    P: AsRef<Path>,
+{
+    properties_with_options_async(path, Options::default()).await
+}
+
+/// Like [`properties_with_options`](crate::properties_with_options), but reads
+/// the EditorConfig files without blocking.
+pub async fn properties_with_options_async<P>(
+    path: P,
+    options: Options<'_>,
+) -> Result<Properties, Error>
+where
+    P: AsRef<Path>,
+{
+    properties_with_fs_async(path, options, &TokioFileSystem).await
+}
+
+/// Like [`properties_with_options`](crate::properties_with_options), but reads
+/// the EditorConfig files from `fs`.
+pub async fn properties_with_fs_async<P, F>(
+    path: P,
+    options: Options<'_>,
+    fs: &F,
+) -> Result<Properties, Error>
+where
+    P: AsRef<Path>,
+    F: AsyncFileSystem,
+{
+    let path = path.as_ref();
+
+    // Read all the EditorConfig files first, then resolve as usual.
+    let behavior = Behavior::of(options.version);
+    let mut documents = HashMap::new();
+    for dir in ancestors(path) {
+        let contents = fs.read_to_string(&dir.join(options.file_name)).await;
+        documents.insert(dir, parse_contents(contents, &behavior)?);
+    }
+
+    resolve(path, &options, |dir| Ok(documents.remove(dir).flatten()))
+}
+
+/// Parses the contents read from an EditorConfig file, if it exists.
+fn parse_contents(
+    contents: io::Result<String>,
+    behavior: &Behavior,
+) -> Result<Option<Document>, Error> {
+    match contents {
+        Ok(contents) => {
+            Ok(Some(Document::parse_with_behavior(&contents, behavior)))
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::Io(e)),
+    }
+}
@@ -0,0 +1,501 @@
+//! A Language Server for EditorConfig files.
+//!
+//! For EditorConfig files, it offers diagnostics (see [`lint`]), completion of
+//! property names and values, hovers (of properties and of section globs),
+//! and formatting. For any other file, it offers a hover with the resolved
+//! properties of the file, and go-to-definition from the name of a property
+//! (e.g., in a comment) to the line which sets it.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use editorconfig_core::{
+    Document, FormatOptions, KNOWN_PROPERTIES, KnownProperty, Line, LineKind,
+    Origin, Severity, TreeOptions, explain, format, lint,
+    resolve_tree_from_source,
+};
+use lsp_server::{Connection, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{
+    Completion, Formatting, GotoDefinition, HoverRequest, Request as _,
+};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams,
+    Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DocumentFormattingParams, Documentation, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+    HoverProviderCapability, Location, MarkupContent, MarkupKind,
+    NumberOrString, OneOf, Position, PublishDiagnosticsParams, Range,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextEdit, Url,
+};
+
+const EC_FILE_NAME: &str = ".editorconfig";
+
+/// The max. number of files listed in the hover of a section.
+const MAX_HOVER_FILES: usize = 20;
+
+type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+
+struct Server {
+    connection: Connection,
+    /// The contents of the open documents.
+    documents: HashMap<Url, String>,
+}
+
+fn main() -> ExitCode {
+    let (connection, io_threads) = Connection::stdio();
+
+    let result = run(connection).and_then(|()| Ok(io_threads.join()?));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("editorconfig-lsp: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(connection: Connection) -> Result<()> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::FULL,
+        )),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["=".to_owned()]),
+            ..Default::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut server = Server { connection, documents: HashMap::new() };
+
+    while let Ok(message) = server.connection.receiver.recv() {
+        match message {
+            Message::Request(request) => {
+                if server.connection.handle_shutdown(&request)? {
+                    break;
+                }
+                server.handle_request(request)?;
+            }
+            Message::Notification(notification) => {
+                server.handle_notification(notification)?;
+            }
+            Message::Response(_response) => {}
+        }
+    }
+
+    Ok(())
+}
+
+impl Server {
+    fn handle_request(&mut self, request: Request) -> Result<()> {
+        let response = match request.method.as_str() {
+            Completion::METHOD => self.respond(request, Self::completion),
+            HoverRequest::METHOD => self.respond(request, Self::hover),
+            GotoDefinition::METHOD => self.respond(request, Self::definition),
+            Formatting::METHOD => self.respond(request, Self::formatting),
+            _ => Response::new_err(
+                request.id,
+                lsp_server::ErrorCode::MethodNotFound as i32,
+                format!("unsupported request `{}`", request.method),
+            ),
+        };
+
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    /// Answers `request` with `handler`, or with an error if its params are
+    /// malformed.
+    fn respond<P, R, F>(&self, request: Request, handler: F) -> Response
+    where
+        P: serde::de::DeserializeOwned,
+        R: serde::Serialize,
+        F: FnOnce(&Self, P) -> R,
+    {
+        match serde_json::from_value(request.params) {
+            Ok(params) => Response::new_ok(request.id, handler(self, params)),
+            Err(e) => Response::new_err(
+                request.id,
+                lsp_server::ErrorCode::InvalidParams as i32,
+                format!("invalid params: {e}"),
+            ),
+        }
+    }
+
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Some(params): Option<DidOpenTextDocumentParams> =
+                    parse_params(notification.params)
+                else {
+                    return Ok(());
+                };
+                let uri = params.text_document.uri;
+                self.documents.insert(uri.clone(), params.text_document.text);
+                self.publish_diagnostics(uri)?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let Some(params): Option<DidChangeTextDocumentParams> =
+                    parse_params(notification.params)
+                else {
+                    return Ok(());
+                };
+                let uri = params.text_document.uri;
+                // We only support full syncs, so the last change is the whole
+                // document.
+                if let Some(change) = params.content_changes.into_iter().last()
+                {
+                    self.documents.insert(uri.clone(), change.text);
+                }
+                self.publish_diagnostics(uri)?;
+            }
+            DidCloseTextDocument::METHOD => {
+                let Some(params): Option<DidCloseTextDocumentParams> =
+                    parse_params(notification.params)
+                else {
+                    return Ok(());
+                };
+                let uri = params.text_document.uri;
+                if self.documents.remove(&uri).is_some() && is_ec_file(&uri) {
+                    self.send_diagnostics(uri, vec![])?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn publish_diagnostics(&self, uri: Url) -> Result<()> {
+        let Some(text) = self.documents.get(&uri) else { return Ok(()) };
+        if !is_ec_file(&uri) {
+            return Ok(());
+        }
+
+        let document = Document::parse(text);
+        let diagnostics = lint(&document)
+            .into_iter()
+            .filter_map(|d| {
+                let line = document.lines.get(d.line.checked_sub(1)?)?;
+                let offset = line
+                    .text
+                    .char_indices()
+                    .nth(d.column - 1)
+                    .map_or(line.text.len(), |(idx, _ch)| idx);
+                let end = line.text.trim_end().len().max(offset);
+
+                let severity = match d.rule.severity() {
+                    Severity::Error => DiagnosticSeverity::ERROR,
+                    Severity::Warning => DiagnosticSeverity::WARNING,
+                };
+
+                Some(Diagnostic {
+                    range: line_range(line, offset, end),
+                    severity: Some(severity),
+                    code: Some(NumberOrString::String(
+                        d.rule.code().to_owned(),
+                    )),
+                    source: Some("editorconfig".to_owned()),
+                    message: d.message,
+                    ..Default::default()
+                })
+            })
+            .collect();
+
+        self.send_diagnostics(uri, diagnostics)
+    }
+
+    fn send_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Result<()> {
+        let params =
+            PublishDiagnosticsParams { uri, diagnostics, version: None };
+        let notification =
+            Notification::new(PublishDiagnostics::METHOD.to_owned(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+
+    fn completion(&self, params: CompletionParams) -> Vec<CompletionItem> {
+        let position = params.text_document_position;
+        let uri = position.text_document.uri;
+        let Some(text) = self.documents.get(&uri) else { return vec![] };
+        if !is_ec_file(&uri) {
+            return vec![];
+        }
+
+        let line = text.lines().nth(position.position.line as usize);
+        let line = line.unwrap_or_default();
+        let before = &line[..byte_offset(line, position.position.character)];
+        let before = before.trim_start();
+
+        if before.starts_with(['[', '#', ';']) {
+            return vec![];
+        }
+
+        if let Some((key, _value)) = before.split_once('=') {
+            let Some(property) = KnownProperty::get(key.trim()) else {
+                return vec![];
+            };
+            return property
+                .keywords()
+                .chain(["unset"])
+                .map(|keyword| CompletionItem {
+                    label: keyword.to_owned(),
+                    kind: Some(CompletionItemKind::VALUE),
+                    ..Default::default()
+                })
+                .collect();
+        }
+
+        KNOWN_PROPERTIES
+            .iter()
+            .map(|property| CompletionItem {
+                label: property.key.to_owned(),
+                kind: Some(CompletionItemKind::PROPERTY),
+                documentation: Some(Documentation::MarkupContent(markdown(
+                    property.description.to_owned(),
+                ))),
+                insert_text: Some(format!("{} = ", property.key)),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        let path = uri.to_file_path().ok()?;
+
+        if !is_ec_file(&uri) {
+            return hover_properties(&path);
+        }
+
+        let text = self.documents.get(&uri)?;
+        let document = Document::parse(text);
+        let line = document.lines.get(position.position.line as usize)?;
+        let offset = byte_offset(&line.text, position.position.character);
+
+        let contents = match &line.kind {
+            LineKind::Pair { key, .. } if key.contains(&offset) => {
+                let property = KnownProperty::get(&line.text[key.clone()])?;
+                let values = property
+                    .keywords()
+                    .map(|k| format!("`{k}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let mut contents =
+                    format!("**{}**\n\n{}", property.key, property.description);
+                if !values.is_empty() {
+                    contents.push_str(&format!("\n\nValues: {values}"));
+                }
+                contents
+            }
+            LineKind::Section { .. } => {
+                let ec_dir = path.parent()?;
+                hover_section_files(ec_dir, line.pattern()?)
+            }
+            _ => return None,
+        };
+
+        Some(Hover {
+            contents: HoverContents::Markup(markdown(contents)),
+            range: None,
+        })
+    }
+
+    fn definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        if is_ec_file(&uri) {
+            return None;
+        }
+
+        // The property named under the cursor (e.g., in a comment).
+        let text = self.documents.get(&uri)?;
+        let line = text.lines().nth(position.position.line as usize)?;
+        let offset = byte_offset(line, position.position.character);
+        let key = word_at(line, offset)?.to_lowercase();
+
+        let explanation = explain(uri.to_file_path().ok()?).ok()?;
+        let Origin::Pair { path, line } = explanation.origins.get(&key)? else {
+            return None;
+        };
+
+        let file = explanation.files.iter().find(|f| &f.path == path)?;
+        let line = file.document.lines.get(line.checked_sub(1)?)?;
+        let LineKind::Pair { key, .. } = &line.kind else { return None };
+
+        Some(GotoDefinitionResponse::Scalar(Location {
+            uri: Url::from_file_path(path).ok()?,
+            range: line_range(line, key.start, key.end),
+        }))
+    }
+
+    fn formatting(
+        &self,
+        params: DocumentFormattingParams,
+    ) -> Option<Vec<TextEdit>> {
+        let uri = params.text_document.uri;
+        let text = self.documents.get(&uri)?;
+        if !is_ec_file(&uri) {
+            return None;
+        }
+
+        let formatted = format(text, FormatOptions::default());
+        if &formatted == text {
+            return Some(vec![]);
+        }
+
+        let end = Position::new(text.lines().count() as u32 + 1, 0);
+        Some(vec![TextEdit {
+            range: Range::new(Position::new(0, 0), end),
+            new_text: formatted,
+        }])
+    }
+}
+
+fn hover_properties(path: &Path) -> Option<Hover> {
+    let explanation = explain(path).ok()?;
+
+    let mut properties: Vec<_> = explanation.properties.iter().collect();
+    properties.sort_unstable();
+
+    let mut contents = String::from("**EditorConfig**\n");
+    for (key, value) in properties {
+        let origin = match explanation.origins.get(key) {
+            Some(Origin::Pair { path, line }) => {
+                format!("{}:{line}", path.display())
+            }
+            _ => "derived".to_owned(),
+        };
+        contents.push_str(&format!("\n- `{key} = {value}` ({origin})"));
+    }
+
+    Some(Hover {
+        contents: HoverContents::Markup(markdown(contents)),
+        range: None,
+    })
+}
+
+fn hover_section_files(ec_dir: &Path, pattern: &str) -> String {
+    let files = match section_files(ec_dir, pattern) {
+        Ok(files) => files,
+        Err(e) => return format!("`[{pattern}]`: {e}"),
+    };
+
+    let mut contents = match files.len() {
+        0 => return format!("`[{pattern}]` doesn't match any file"),
+        1 => format!("`[{pattern}]` matches 1 file:\n"),
+        n => format!("`[{pattern}]` matches {n} files:\n"),
+    };
+    for file in files.iter().take(MAX_HOVER_FILES) {
+        let file: PathBuf = file.strip_prefix(ec_dir).unwrap_or(file).into();
+        contents.push_str(&format!("\n- `{}`", file.display()));
+    }
+    if MAX_HOVER_FILES < files.len() {
+        contents.push_str("\n- …");
+    }
+
+    contents
+}
+
+/// Lists the files under `ec_dir` (skipping the ignored files) which the
+/// section glob `pattern`, of the EditorConfig file in `ec_dir`, matches.
+fn section_files(
+    ec_dir: &Path,
+    pattern: &str,
+) -> std::result::Result<Vec<PathBuf>, editorconfig_core::Error> {
+    // The section on its own, with a pair telling the files it applies to.
+    const KEY: &str = "matched";
+    let source = HashMap::from([(
+        ec_dir.join(EC_FILE_NAME),
+        format!("root = true\n[{pattern}]\n{KEY} = true\n"),
+    )]);
+
+    let options = TreeOptions::default().with_ignore_files(true);
+    resolve_tree_from_source(ec_dir, options, &source)?
+        .into_iter()
+        .filter_map(|(file, result)| match result {
+            Ok(properties) => properties.contains_key(KEY).then_some(Ok(file)),
+            Err(e) => Some(Err(e)),
+        })
+        .collect()
+}
+
+/// The params of a notification, or `None` if they're malformed (in which
+/// case the notification is ignored, as there's no one to report it to).
+fn parse_params<P>(params: serde_json::Value) -> Option<P>
+where
+    P: serde::de::DeserializeOwned,
+{
+    serde_json::from_value(params).ok()
+}
+
+fn is_ec_file(uri: &Url) -> bool {
+    uri.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .is_some_and(|name| name == EC_FILE_NAME)
+}
+
+fn markdown(value: String) -> MarkupContent {
+    MarkupContent { kind: MarkupKind::Markdown, value }
+}
+
+/// The range between the byte offsets `start` and `end` of `line`.
+fn line_range(line: &Line, start: usize, end: usize) -> Range {
+    let number = line.number as u32 - 1;
+    let col = |offset: usize| line.text[..offset].encode_utf16().count() as u32;
+    Range::new(
+        Position::new(number, col(start)),
+        Position::new(number, col(end)),
+    )
+}
+
+/// The word (of alphanumeric characters and `_`) around the byte offset
+/// `offset` of `line`.
+fn word_at(line: &str, offset: usize) -> Option<&str> {
+    let is_word = |ch: char| ch.is_alphanumeric() || ch == '_';
+    let start = line[..offset]
+        .char_indices()
+        .rev()
+        .find(|&(_idx, ch)| !is_word(ch))
+        .map_or(0, |(idx, ch)| idx + ch.len_utf8());
+    let end = line[offset..]
+        .find(|ch| !is_word(ch))
+        .map_or(line.len(), |idx| offset + idx);
+    (start < end).then(|| &line[start..end])
+}
+
+/// The byte offset of the UTF-16 column `character` of `line`.
+fn byte_offset(line: &str, character: u32) -> usize {
+    let mut units = 0;
+    for (idx, ch) in line.char_indices() {
+        if character as usize <= units {
+            return idx;
+        }
+        units += ch.len_utf16();
+    }
+    line.len()
+}
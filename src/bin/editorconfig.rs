@@ -1,61 +1,718 @@
-use clap::Parser as _;
+use std::fs;
+use std::io::{self, BufWriter, Read as _, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::error::ErrorKind;
+use clap::{CommandFactory as _, Parser as _};
 use editorconfig_core::{
-    MAX_VERSION, Options, Version, properties_with_options,
+    Explanation, FileSystem, FormatOptions, MAX_VERSION, Mirror, Options,
+    Origin, Properties, PropertyChange, Snapshot, TreeOptions, Version,
+    clang_format_config, diff_tree, explain_with_options, files_with_property,
+    format, infer, lint_file, prettier_config, properties_with_options,
+    resolve_tree, rustfmt_config, scaffold, vscode_settings,
 };
 
+/// Some of the files could not be processed.
+const EXIT_FILE_ERROR: u8 = 1;
+/// The input itself (e.g., stdin or stdout) could not be processed (`EX_IOERR`
+/// of `sysexits.h`, since clap exits with `2` for usage errors).
+const EXIT_IO_ERROR: u8 = 74;
+
+const EXIT_CODES: &str = "\
+Exit codes:
+  0   Success
+  1   Some of the files could not be processed
+  2   Invalid arguments
+  74  The input or the output (e.g., stdin or stdout) could not be processed";
+
 #[derive(clap::Parser)]
+#[command(
+    name = "editorconfig",
+    args_conflicts_with_subcommands = true,
+    after_help = EXIT_CODES
+)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(short, long)]
     version: bool,
 
     /// An EditorConfig file path.
-    #[arg(short = 'f')]
+    #[arg(short = 'f', global = true)]
     ec_file_name: Option<String>,
 
     /// EditorConfig version to use.
-    #[arg(short = 'b')]
+    #[arg(short = 'b', global = true)]
     ec_version: Option<Version>,
 
-    files: Vec<String>,
+    /// Leave out the properties which are `unset`.
+    #[arg(long, global = true)]
+    remove_unset: bool,
+
+    /// Take the EditorConfig files which didn't change from a snapshot (see
+    /// the `snapshot` command).
+    #[arg(long)]
+    snapshot: Option<PathBuf>,
+
+    /// Read the file paths from stdin, one per line (in addition to `files`).
+    #[arg(long)]
+    stdin: bool,
+
+    /// The paths read from stdin are NUL-delimited rather than
+    /// newline-delimited.
+    #[arg(short = '0', long, requires = "stdin")]
+    null: bool,
+
+    files: Vec<PathBuf>,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Explain how the properties of a file are resolved.
+    Explain { file: String },
+    /// Lint EditorConfig files (directories are searched recursively).
+    Lint {
+        #[arg(default_value = ".")]
+        paths: Vec<PathBuf>,
+    },
+    /// Format EditorConfig files in place (directories are searched
+    /// recursively).
+    Fmt {
+        /// Don't write the files, but fail if any of them isn't formatted.
+        #[arg(long)]
+        check: bool,
+        /// Sort the pairs of each section by key.
+        #[arg(long)]
+        sort_keys: bool,
+        #[arg(default_value = ".")]
+        paths: Vec<PathBuf>,
+    },
+    /// Propose an EditorConfig file from the conventions detected in a
+    /// directory tree.
+    Init {
+        /// Write the proposal to the EditorConfig file in `dir` (which
+        /// mustn't exist yet), instead of printing it.
+        #[arg(long)]
+        write: bool,
+        #[arg(default_value = ".")]
+        dir: PathBuf,
+    },
+    /// Compile the EditorConfig files under a directory (and above it) into a
+    /// snapshot, for resolving without parsing them.
+    Snapshot {
+        /// The file to write the snapshot to.
+        #[arg(short, long)]
+        output: PathBuf,
+        #[arg(default_value = ".")]
+        root: PathBuf,
+    },
+    /// List the files under a directory whose properties contain a key (set to
+    /// a value, if given).
+    Where {
+        #[arg(value_name = "KEY[=VALUE]")]
+        query: String,
+        /// Skip the files ignored by ignore files, e.g., `.gitignore`.
+        #[arg(long)]
+        ignore_files: bool,
+        #[arg(default_value = ".")]
+        root: PathBuf,
+    },
+    /// Show how the properties of the files under a directory change between
+    /// two sets of EditorConfig files.
+    Diff {
+        /// A directory with the old EditorConfig files, at the same paths as
+        /// under `root`.
+        #[arg(long)]
+        old: PathBuf,
+        /// A directory with the new EditorConfig files, at the same paths as
+        /// under `root` (defaults to `root` itself).
+        #[arg(long)]
+        new: Option<PathBuf>,
+        /// Skip the files ignored by ignore files, e.g., `.gitignore`.
+        #[arg(long)]
+        ignore_files: bool,
+        #[arg(default_value = ".")]
+        root: PathBuf,
+    },
+    /// Convert the properties of a file to the configuration of another tool.
+    Export {
+        #[arg(value_enum)]
+        format: ExportFormat,
+        /// The file (or, for `vscode`, `LANGUAGE=FILE` pairs, e.g.
+        /// `rust=src/main.rs`, for the language overrides).
+        #[arg(required = true)]
+        files: Vec<String>,
+    },
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ExportFormat {
+    /// `rustfmt.toml`.
+    Rustfmt,
+    /// `.prettierrc` (JSON).
+    Prettier,
+    /// `.clang-format`.
+    ClangFormat,
+    /// VS Code's `settings.json`.
+    Vscode,
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args = Cli::parse();
 
     if args.version {
         print_version();
-        return;
+        return ExitCode::SUCCESS;
     }
 
-    for file in args.files.iter() {
-        if 1 < args.files.len() {
-            println!("[{file}]");
+    let options = args.options();
+
+    match args.command.as_ref() {
+        Some(Command::Explain { file }) => explain(file, options),
+        Some(Command::Lint { paths }) => lint(paths, options),
+        Some(&Command::Fmt { check, sort_keys, ref paths }) => {
+            let fmt_options =
+                FormatOptions::default().with_sort_keys(sort_keys);
+            fmt(paths, check, fmt_options, options)
+        }
+        Some(&Command::Init { write, ref dir }) => init(dir, write, options),
+        Some(Command::Snapshot { output, root }) => {
+            snapshot(root, output, options)
         }
+        Some(&Command::Where { ref query, ignore_files, ref root }) => {
+            let tree_options = TreeOptions::default()
+                .with_options(options)
+                .with_ignore_files(ignore_files);
+            find(root, query, tree_options)
+        }
+        Some(&Command::Diff { ref old, ref new, ignore_files, ref root }) => {
+            let tree_options = TreeOptions::default()
+                .with_options(options)
+                .with_ignore_files(ignore_files);
+            diff(root, old, new.as_deref(), tree_options)
+        }
+        Some(&Command::Export { format, ref files }) => {
+            export(format, files, options)
+        }
+        None => print_files(&args, options),
+    }
+}
 
+impl Cli {
+    fn options(&self) -> Options<'_> {
         let mut options = Options::default();
-        if let Some(file_name) = args.ec_file_name.as_ref() {
+        if let Some(file_name) = self.ec_file_name.as_ref() {
             options.file_name = file_name;
         }
-        if let Some(version) = args.ec_version {
+        if let Some(version) = self.ec_version {
             options.version = version;
         }
+        options.remove_unset = self.remove_unset;
+        options
+    }
+}
+
+fn print_files(args: &Cli, options: Options) -> ExitCode {
+    let mut files = args.files.clone();
+    if args.stdin {
+        match read_stdin_paths(args.null) {
+            Ok(paths) => files.extend(paths),
+            Err(e) => {
+                eprintln!("editorconfig: failed to read stdin: {e}");
+                return ExitCode::from(EXIT_IO_ERROR);
+            }
+        }
+    }
+
+    // A snapshot which can't be read is only slower to do without.
+    let snapshot = args.snapshot.as_ref().and_then(|path| {
+        Snapshot::read(path)
+            .inspect_err(|e| {
+                eprintln!("editorconfig: {}: {e}", path.display());
+            })
+            .ok()
+    });
+
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    let mut failed = false;
+
+    for file in files.iter() {
+        let result = match &snapshot {
+            Some(snapshot) => snapshot.properties(file, options),
+            None => properties_with_options(file, options),
+        };
+
+        let written = if 1 < files.len() {
+            writeln!(stdout, "[{}]", file.display())
+        } else {
+            Ok(())
+        };
+
+        let written = written.and_then(|()| match result {
+            Ok(props) => print_pairs(&mut stdout, &props),
+            Err(e) => {
+                // Keep the outputs in order, so flush what we've got so far
+                // before reporting.
+                failed = true;
+                stdout.flush()?;
+                eprintln!("editorconfig: {}: {e}", file.display());
+                Ok(())
+            }
+        });
+
+        if written.is_err() {
+            return ExitCode::from(EXIT_IO_ERROR);
+        }
+    }
 
-        print_pairs(file, options);
+    if stdout.flush().is_err() {
+        return ExitCode::from(EXIT_IO_ERROR);
     }
+
+    if failed { ExitCode::from(EXIT_FILE_ERROR) } else { ExitCode::SUCCESS }
+}
+
+/// Reads the (non-empty) paths from stdin, delimited by either NUL or newline.
+///
+/// The paths are taken as bytes, so a path which isn't valid unicode only
+/// fails by itself (when resolving it).
+fn read_stdin_paths(null: bool) -> io::Result<Vec<PathBuf>> {
+    let mut input = vec![];
+    io::stdin().lock().read_to_end(&mut input)?;
+
+    let delimiter = if null { b'\0' } else { b'\n' };
+    let paths = input.split(|&b| b == delimiter).map(|path| {
+        // Like `str::lines`, which strips the `\r` of a `\r\n`.
+        if null { path } else { path.strip_suffix(b"\r").unwrap_or(path) }
+    });
+
+    Ok(paths.filter(|p| !p.is_empty()).map(path_from_bytes).collect())
 }
 
-fn print_pairs(file: &str, options: Options) {
-    let props = properties_with_options(file, options).unwrap();
+#[cfg(unix)]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::os::unix::ffi::OsStrExt as _;
+
+    std::ffi::OsStr::from_bytes(bytes).into()
+}
+
+/// Elsewhere, paths are not arbitrary bytes, so stdin is taken as UTF-8.
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    String::from_utf8_lossy(bytes).into_owned().into()
+}
 
+fn print_pairs<W: Write>(out: &mut W, props: &Properties) -> io::Result<()> {
     let mut props = props.iter().collect::<Vec<_>>();
     // The testing suite expects them to be sorted.
     props.sort_unstable_by_key(|&(key, _value)| key);
 
     for (key, value) in props {
-        println!("{key}={value}");
+        writeln!(out, "{key}={value}")?;
+    }
+
+    Ok(())
+}
+
+fn explain(file: &str, options: Options) -> ExitCode {
+    let explanation = match explain_with_options(file, options) {
+        Ok(explanation) => explanation,
+        Err(e) => {
+            eprintln!("editorconfig: {file}: {e}");
+            return ExitCode::from(EXIT_FILE_ERROR);
+        }
+    };
+
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    match print_explanation(&mut stdout, &explanation)
+        .and_then(|()| stdout.flush())
+    {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::from(EXIT_IO_ERROR),
+    }
+}
+
+fn print_explanation<W: Write>(
+    out: &mut W,
+    explanation: &Explanation,
+) -> io::Result<()> {
+    for file in explanation.files.iter() {
+        write!(out, "{}", file.path.display())?;
+        if let Some(line) = file.root {
+            write!(out, " (root = true at line {line})")?;
+        }
+        if file.discarded {
+            write!(out, " (discarded by a root below)")?;
+        }
+        writeln!(out)?;
+
+        for section in file.sections.iter() {
+            let matched =
+                if section.is_match { "matched" } else { "not matched" };
+            writeln!(
+                out,
+                "  {}: [{}] {matched}",
+                section.line, section.pattern
+            )?;
+
+            for pair in section.pairs.iter() {
+                write!(
+                    out,
+                    "    {}: {} = {}",
+                    pair.line, pair.key, pair.value
+                )?;
+                match pair.overridden_by.as_ref() {
+                    Some(origin) => writeln!(
+                        out,
+                        " (overridden by {})",
+                        fmt_origin(origin)
+                    )?,
+                    None => writeln!(out)?,
+                }
+            }
+        }
+    }
+
+    if !explanation.files.is_empty() {
+        writeln!(out)?;
+    }
+
+    let mut props = explanation.properties.iter().collect::<Vec<_>>();
+    props.sort_unstable_by_key(|&(key, _value)| key);
+
+    for (key, value) in props {
+        let origin = explanation.origins.get(key).unwrap_or(&Origin::Derived);
+        writeln!(out, "{key}={value} ({})", fmt_origin(origin))?;
+    }
+
+    Ok(())
+}
+
+fn fmt_origin(origin: &Origin) -> String {
+    match origin {
+        Origin::Pair { path, line } => format!("{}:{line}", path.display()),
+        Origin::Derived => "derived".to_owned(),
+    }
+}
+
+fn lint(paths: &[PathBuf], options: Options) -> ExitCode {
+    let mut ec_files = vec![];
+    for path in paths {
+        match find_ec_files(path, options) {
+            Ok(files) => ec_files.extend(files),
+            Err(e) => {
+                eprintln!("editorconfig: {}: {e}", path.display());
+                return ExitCode::from(EXIT_IO_ERROR);
+            }
+        }
+    }
+
+    let mut failed = false;
+    for ec_file in ec_files.iter() {
+        match lint_file(ec_file, options) {
+            Ok(diagnostics) => {
+                for diagnostic in diagnostics.iter() {
+                    println!("{}:{diagnostic}", ec_file.display());
+                }
+                failed |= !diagnostics.is_empty();
+            }
+            Err(e) => {
+                eprintln!("editorconfig: {}: {e}", ec_file.display());
+                failed = true;
+            }
+        }
+    }
+
+    if failed { ExitCode::from(EXIT_FILE_ERROR) } else { ExitCode::SUCCESS }
+}
+
+fn fmt(
+    paths: &[PathBuf],
+    check: bool,
+    fmt_options: FormatOptions,
+    options: Options,
+) -> ExitCode {
+    let mut ec_files = vec![];
+    for path in paths {
+        match find_ec_files(path, options) {
+            Ok(files) => ec_files.extend(files),
+            Err(e) => {
+                eprintln!("editorconfig: {}: {e}", path.display());
+                return ExitCode::from(EXIT_IO_ERROR);
+            }
+        }
+    }
+
+    let mut failed = false;
+    for ec_file in ec_files.iter() {
+        let result = fs::read_to_string(ec_file).and_then(|contents| {
+            let formatted = format(&contents, fmt_options);
+            if formatted == contents {
+                Ok(())
+            } else if check {
+                println!("{}", ec_file.display());
+                failed = true;
+                Ok(())
+            } else {
+                fs::write(ec_file, formatted)
+            }
+        });
+
+        if let Err(e) = result {
+            eprintln!("editorconfig: {}: {e}", ec_file.display());
+            failed = true;
+        }
+    }
+
+    if failed { ExitCode::from(EXIT_FILE_ERROR) } else { ExitCode::SUCCESS }
+}
+
+fn snapshot(root: &Path, output: &Path, options: Options) -> ExitCode {
+    let written = Snapshot::compile(root, options)
+        .and_then(|snapshot| snapshot.write(output));
+
+    match written {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("editorconfig: {}: {e}", root.display());
+            ExitCode::from(EXIT_FILE_ERROR)
+        }
+    }
+}
+
+fn find(root: &Path, query: &str, tree_options: TreeOptions) -> ExitCode {
+    let (key, value) = match query.split_once('=') {
+        Some((key, value)) => (key, Some(value)),
+        None => (query, None),
+    };
+
+    let entries = match files_with_property(root, key, value, tree_options) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("editorconfig: {}: {e}", root.display());
+            return ExitCode::from(EXIT_FILE_ERROR);
+        }
+    };
+
+    // The paths are absolute, so print them as they're under `root`.
+    let abs_root = std::path::absolute(root).unwrap_or_default();
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    let mut failed = false;
+
+    for (path, result) in entries.iter() {
+        let path =
+            path.strip_prefix(&abs_root).map_or(path.clone(), |p| root.join(p));
+        let written = match result {
+            Ok(_properties) => writeln!(stdout, "{}", path.display()),
+            Err(e) => {
+                failed = true;
+                stdout.flush().inspect(|()| {
+                    eprintln!("editorconfig: {}: {e}", path.display());
+                })
+            }
+        };
+
+        if written.is_err() {
+            return ExitCode::from(EXIT_IO_ERROR);
+        }
+    }
+
+    if stdout.flush().is_err() {
+        return ExitCode::from(EXIT_IO_ERROR);
+    }
+
+    if failed { ExitCode::from(EXIT_FILE_ERROR) } else { ExitCode::SUCCESS }
+}
+
+fn diff(
+    root: &Path,
+    old: &Path,
+    new: Option<&Path>,
+    tree_options: TreeOptions,
+) -> ExitCode {
+    let abs_root = match std::path::absolute(root) {
+        Ok(abs_root) => abs_root,
+        Err(e) => {
+            eprintln!("editorconfig: {}: {e}", root.display());
+            return ExitCode::from(EXIT_IO_ERROR);
+        }
+    };
+    let mirror =
+        |dir: &Path| Mirror { root: abs_root.clone(), dir: dir.into() };
+
+    let diffs = match new {
+        Some(new) => diff_tree(root, &mirror(old), &mirror(new), tree_options),
+        None => diff_tree(root, &mirror(old), &FileSystem, tree_options),
+    };
+    let diffs = match diffs {
+        Ok(diffs) => diffs,
+        Err(e) => {
+            eprintln!("editorconfig: {}: {e}", root.display());
+            return ExitCode::from(EXIT_FILE_ERROR);
+        }
+    };
+
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    let mut failed = false;
+
+    for (path, result) in diffs.iter() {
+        let path =
+            path.strip_prefix(&abs_root).map_or(path.clone(), |p| root.join(p));
+        let written = match result {
+            Ok(changes) => print_changes(&mut stdout, &path, changes),
+            Err(e) => {
+                failed = true;
+                stdout.flush().inspect(|()| {
+                    eprintln!("editorconfig: {}: {e}", path.display());
+                })
+            }
+        };
+
+        if written.is_err() {
+            return ExitCode::from(EXIT_IO_ERROR);
+        }
+    }
+
+    if stdout.flush().is_err() {
+        return ExitCode::from(EXIT_IO_ERROR);
+    }
+
+    if failed { ExitCode::from(EXIT_FILE_ERROR) } else { ExitCode::SUCCESS }
+}
+
+/// Prints the changes like a diff of the `key=value` lines of the file.
+fn print_changes<W: Write>(
+    out: &mut W,
+    path: &Path,
+    changes: &[PropertyChange],
+) -> io::Result<()> {
+    writeln!(out, "[{}]", path.display())?;
+    for change in changes {
+        if let Some(old) = change.old.as_ref() {
+            writeln!(out, "-{}={old}", change.key)?;
+        }
+        if let Some(new) = change.new.as_ref() {
+            writeln!(out, "+{}={new}", change.key)?;
+        }
+    }
+    Ok(())
+}
+
+fn export(
+    format: ExportFormat,
+    files: &[String],
+    options: Options,
+) -> ExitCode {
+    let files: Vec<(&str, &str)> = match format {
+        ExportFormat::Vscode => {
+            let pairs: Option<Vec<_>> =
+                files.iter().map(|file| file.split_once('=')).collect();
+            let Some(pairs) = pairs else {
+                usage_error("`vscode` expects LANGUAGE=FILE pairs");
+            };
+            pairs
+        }
+        _ if 1 < files.len() => usage_error("expected a single file"),
+        _ => files.iter().map(|file| ("", file.as_str())).collect(),
+    };
+
+    let mut resolved = vec![];
+    for &(language, file) in files.iter() {
+        match properties_with_options(file, options) {
+            Ok(properties) => resolved.push((language, properties)),
+            Err(e) => {
+                eprintln!("editorconfig: {file}: {e}");
+                return ExitCode::from(EXIT_FILE_ERROR);
+            }
+        }
+    }
+
+    let config = match format {
+        ExportFormat::Rustfmt => rustfmt_config(&resolved[0].1),
+        ExportFormat::Prettier => prettier_config(&resolved[0].1),
+        ExportFormat::ClangFormat => clang_format_config(&resolved[0].1),
+        ExportFormat::Vscode => vscode_settings(
+            resolved
+                .iter()
+                .map(|(language, properties)| (*language, properties)),
+        ),
+    };
+
+    let mut stdout = io::stdout().lock();
+    match stdout.write_all(config.as_bytes()).and_then(|()| stdout.flush()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::from(EXIT_IO_ERROR),
+    }
+}
+
+/// Exits like for any other invalid arguments of `export`.
+fn usage_error(message: &str) -> ! {
+    let mut cli = Cli::command();
+    cli.build();
+    let export = cli.find_subcommand_mut("export").expect("a subcommand");
+    export.error(ErrorKind::ValueValidation, message).exit()
+}
+
+fn init(dir: &Path, write: bool, options: Options) -> ExitCode {
+    let conventions = match infer(dir) {
+        Ok(conventions) => conventions,
+        Err(e) => {
+            eprintln!("editorconfig: {}: {e}", dir.display());
+            return ExitCode::from(EXIT_FILE_ERROR);
+        }
+    };
+    let contents = scaffold(&conventions);
+
+    if !write {
+        print!("{contents}");
+        return ExitCode::SUCCESS;
+    }
+
+    let ec_file = dir.join(options.file_name);
+    let written = fs::File::create_new(&ec_file)
+        .and_then(|mut f| f.write_all(contents.as_bytes()));
+
+    match written {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("editorconfig: {}: {e}", ec_file.display());
+            ExitCode::from(EXIT_IO_ERROR)
+        }
     }
 }
 
+/// Lists `path` if it's a file, or the EditorConfig files under it (skipping
+/// `.git` directories and the files ignored by ignore files, e.g.,
+/// `.gitignore`) if it's a directory.
+fn find_ec_files(
+    path: &Path,
+    options: Options,
+) -> Result<Vec<PathBuf>, editorconfig_core::Error> {
+    use editorconfig_core::Error;
+
+    if !fs::metadata(path).map_err(Error::Io)?.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let tree_options =
+        TreeOptions::default().with_options(options).with_ignore_files(true);
+    let entries = resolve_tree(path, tree_options)?;
+
+    // The paths are absolute, so keep them as they're under `path`.
+    let abs_path = std::path::absolute(path).map_err(Error::Io)?;
+    let ec_files = entries
+        .into_iter()
+        .map(|(file, _properties)| file)
+        .filter(|file| file.file_name().is_some_and(|n| n == options.file_name))
+        .map(|file| {
+            file.strip_prefix(&abs_path).map_or(file.clone(), |f| path.join(f))
+        })
+        .collect();
+    Ok(ec_files)
+}
+
 fn print_version() {
     println!("EditorConfig Rust Core Version {MAX_VERSION}");
 }
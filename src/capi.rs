@@ -0,0 +1,362 @@
+//! A C API mirroring the one of the [C core]'s `editorconfig.h`, so this crate
+//! can be linked instead of `libeditorconfig`.
+//!
+//! [C core]: https://github.com/editorconfig/editorconfig-core-c
+
+use std::ffi::{CStr, CString, c_char, c_int};
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use crate::glob::Glob;
+use crate::{
+    Behavior, LineKind, MAX_VERSION, Options, Version, ancestors,
+    normalize_path, properties_with_options, read_document,
+};
+
+/// An EditorConfig file couldn't be read (or the path isn't valid unicode).
+pub const EDITORCONFIG_PARSE_IO_ERROR: c_int = -1;
+/// The path of the file to parse is not a full path.
+pub const EDITORCONFIG_PARSE_NOT_FULL_PATH: c_int = -2;
+/// A memory error occurred.
+pub const EDITORCONFIG_PARSE_MEMORY_ERROR: c_int = -3;
+/// The version set in the handle is greater than the current version.
+pub const EDITORCONFIG_PARSE_VERSION_TOO_NEW: c_int = -4;
+
+/// The state of a parse: its options, and then its results.
+///
+/// Created by [`editorconfig_handle_init`], and destroyed by
+/// [`editorconfig_handle_destroy`].
+pub struct Handle {
+    conf_file_name: Option<CString>,
+    /// The version to use (`0.0.0` for [`MAX_VERSION`]).
+    version: Version,
+    err_file: Option<CString>,
+    name_values: Vec<(CString, CString)>,
+}
+
+/// A pointer to a [`Handle`], as named by `editorconfig.h`.
+#[allow(non_camel_case_types)]
+pub type editorconfig_handle = *mut Handle;
+
+/// Creates a handle (which must be destroyed with
+/// [`editorconfig_handle_destroy`]).
+#[unsafe(no_mangle)]
+pub extern "C" fn editorconfig_handle_init() -> editorconfig_handle {
+    Box::into_raw(Box::new(Handle {
+        conf_file_name: None,
+        version: Version::new(0, 0, 0),
+        err_file: None,
+        name_values: vec![],
+    }))
+}
+
+/// Destroys the handle `h`, returning `0` (or `-1` if `h` is null).
+///
+/// # Safety
+///
+/// `h` must be null or a handle from [`editorconfig_handle_init`], which
+/// wasn't destroyed yet. The strings obtained through it become dangling.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn editorconfig_handle_destroy(
+    h: editorconfig_handle,
+) -> c_int {
+    if h.is_null() {
+        return -1;
+    }
+    // SAFETY: the caller guarantees `h` is a live handle.
+    drop(unsafe { Box::from_raw(h) });
+    0
+}
+
+/// The name of the EditorConfig files, or null if it wasn't set (i.e.,
+/// `.editorconfig`).
+///
+/// # Safety
+///
+/// `h` must be a live handle. The string lives until the name is set again,
+/// or the handle is destroyed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn editorconfig_handle_get_conf_file_name(
+    h: editorconfig_handle,
+) -> *const c_char {
+    // SAFETY: the caller guarantees `h` is a live handle.
+    let h = unsafe { &*h };
+    h.conf_file_name.as_deref().map_or(ptr::null(), CStr::as_ptr)
+}
+
+/// Sets the name of the EditorConfig files (null for `.editorconfig`).
+///
+/// # Safety
+///
+/// `h` must be a live handle, and `conf_file_name` must be null or a
+/// NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn editorconfig_handle_set_conf_file_name(
+    h: editorconfig_handle,
+    conf_file_name: *const c_char,
+) {
+    // SAFETY: the caller guarantees `h` is a live handle.
+    let h = unsafe { &mut *h };
+    h.conf_file_name = (!conf_file_name.is_null())
+        // SAFETY: the caller guarantees it's a NUL-terminated string.
+        .then(|| unsafe { CStr::from_ptr(conf_file_name) }.to_owned());
+}
+
+/// Gets the version set in the handle (`0.0.0` if it wasn't set).
+///
+/// # Safety
+///
+/// `h` must be a live handle, and each of `major`, `minor` and `patch` must be
+/// null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn editorconfig_handle_get_version(
+    h: editorconfig_handle,
+    major: *mut c_int,
+    minor: *mut c_int,
+    patch: *mut c_int,
+) {
+    // SAFETY: the caller guarantees `h` is a live handle, and the pointers are
+    // valid.
+    unsafe { write_version((*h).version, major, minor, patch) };
+}
+
+/// Sets the version to use (the current version is used if it's `0.0.0`, or
+/// isn't set).
+///
+/// # Safety
+///
+/// `h` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn editorconfig_handle_set_version(
+    h: editorconfig_handle,
+    major: c_int,
+    minor: c_int,
+    patch: c_int,
+) {
+    let [major, minor, patch] = [major, minor, patch].map(|n| n.max(0) as u32);
+    // SAFETY: the caller guarantees `h` is a live handle.
+    unsafe { (*h).version = Version::new(major, minor, patch) };
+}
+
+/// The EditorConfig file in which the last parse error occurred, or null.
+///
+/// # Safety
+///
+/// `h` must be a live handle. The string lives until the next parse, or until
+/// the handle is destroyed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn editorconfig_handle_get_err_file(
+    h: editorconfig_handle,
+) -> *const c_char {
+    // SAFETY: the caller guarantees `h` is a live handle.
+    let h = unsafe { &*h };
+    h.err_file.as_deref().map_or(ptr::null(), CStr::as_ptr)
+}
+
+/// The number of properties the last parse found.
+///
+/// # Safety
+///
+/// `h` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn editorconfig_handle_get_name_value_count(
+    h: editorconfig_handle,
+) -> c_int {
+    // SAFETY: the caller guarantees `h` is a live handle.
+    let h = unsafe { &*h };
+    c_int::try_from(h.name_values.len()).unwrap_or(c_int::MAX)
+}
+
+/// Gets the name and the value of the `n`th property (sorted by name) the last
+/// parse found. Both are set to null if there's no such property.
+///
+/// # Safety
+///
+/// `h` must be a live handle, and `name` and `value` must be null or valid for
+/// writes. The strings live until the next parse, or until the handle is
+/// destroyed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn editorconfig_handle_get_name_value(
+    h: editorconfig_handle,
+    n: c_int,
+    name: *mut *const c_char,
+    value: *mut *const c_char,
+) {
+    // SAFETY: the caller guarantees `h` is a live handle.
+    let h = unsafe { &*h };
+    let pair = usize::try_from(n).ok().and_then(|n| h.name_values.get(n));
+    let (n, v) = pair.map_or((ptr::null(), ptr::null()), |(n, v)| {
+        (n.as_ptr(), v.as_ptr())
+    });
+
+    // SAFETY: the caller guarantees the pointers are valid.
+    unsafe {
+        if !name.is_null() {
+            *name = n;
+        }
+        if !value.is_null() {
+            *value = v;
+        }
+    }
+}
+
+/// Resolves the properties of the file at `full_filename` (a full path) into
+/// `h`.
+///
+/// Returns `0` on success, the line number of the parse error (see
+/// [`editorconfig_handle_get_err_file`]) if an EditorConfig file couldn't be
+/// parsed (i.e., it has a malformed line, or a section glob which can't be
+/// compiled), or one of the negative `EDITORCONFIG_PARSE_*` errors.
+///
+/// # Safety
+///
+/// `full_filename` must be a NUL-terminated string, and `h` must be a live
+/// handle. The strings previously obtained through `h` become dangling.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn editorconfig_parse(
+    full_filename: *const c_char,
+    h: editorconfig_handle,
+) -> c_int {
+    // SAFETY: the caller guarantees `h` is a live handle.
+    let h = unsafe { &mut *h };
+    h.err_file = None;
+    h.name_values.clear();
+
+    // SAFETY: the caller guarantees it's a NUL-terminated string.
+    let full_filename = unsafe { CStr::from_ptr(full_filename) };
+    let Ok(full_filename) = full_filename.to_str() else {
+        return EDITORCONFIG_PARSE_IO_ERROR;
+    };
+    let path = Path::new(full_filename);
+    if !path.is_absolute() {
+        return EDITORCONFIG_PARSE_NOT_FULL_PATH;
+    }
+
+    let version = match h.version {
+        Version { major: 0, minor: 0, patch: 0 } => MAX_VERSION,
+        version if !version.is_supported() => {
+            return EDITORCONFIG_PARSE_VERSION_TOO_NEW;
+        }
+        version => version,
+    };
+    let file_name = match &h.conf_file_name {
+        Some(name) => match name.to_str() {
+            Ok(name) => name,
+            Err(_e) => return EDITORCONFIG_PARSE_IO_ERROR,
+        },
+        None => Options::default().file_name,
+    };
+    let options =
+        Options::default().with_file_name(file_name).with_version(version);
+
+    // Like `libeditorconfig`, fail on malformed lines, which are otherwise
+    // ignored.
+    if let Some((ec_file, line)) = find_parse_error(path, &options) {
+        let ec_file = ec_file.into_os_string().into_string();
+        h.err_file = ec_file.ok().and_then(|f| CString::new(f).ok());
+        return c_int::try_from(line).unwrap_or(c_int::MAX);
+    }
+
+    match properties_with_options(path, options) {
+        Ok(properties) => {
+            let mut name_values: Vec<_> = properties
+                .into_iter()
+                .filter_map(|(name, value)| {
+                    Some((CString::new(name).ok()?, CString::new(value).ok()?))
+                })
+                .collect();
+            name_values.sort_unstable();
+            h.name_values = name_values;
+            0
+        }
+        // The errors which fail the parse are found above, unless the files
+        // changed in between.
+        Err(_e) => EDITORCONFIG_PARSE_IO_ERROR,
+    }
+}
+
+/// A message describing the error `err_num`, returned by
+/// [`editorconfig_parse`].
+#[unsafe(no_mangle)]
+pub extern "C" fn editorconfig_get_error_msg(err_num: c_int) -> *const c_char {
+    let msg = match err_num {
+        0 => c"No error occurred.",
+        1.. => c"Failed to parse file.",
+        EDITORCONFIG_PARSE_NOT_FULL_PATH => {
+            c"Input file must be a full path name."
+        }
+        EDITORCONFIG_PARSE_MEMORY_ERROR => c"Memory error.",
+        EDITORCONFIG_PARSE_VERSION_TOO_NEW => {
+            c"Required version is greater than the current version."
+        }
+        EDITORCONFIG_PARSE_IO_ERROR => c"Failed to read file.",
+        _ => c"Unknown error.",
+    };
+    msg.as_ptr()
+}
+
+/// Gets the version of the library (i.e., [`MAX_VERSION`]).
+///
+/// # Safety
+///
+/// Each of `major`, `minor` and `patch` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn editorconfig_get_version(
+    major: *mut c_int,
+    minor: *mut c_int,
+    patch: *mut c_int,
+) {
+    // SAFETY: the caller guarantees the pointers are valid.
+    unsafe { write_version(MAX_VERSION, major, minor, patch) };
+}
+
+/// The suffix of the version of the library (e.g., `-beta`), which is empty.
+#[unsafe(no_mangle)]
+pub extern "C" fn editorconfig_get_version_suffix() -> *const c_char {
+    c"".as_ptr()
+}
+
+/// # Safety
+///
+/// Each of `major`, `minor` and `patch` must be null or valid for writes.
+unsafe fn write_version(
+    version: Version,
+    major: *mut c_int,
+    minor: *mut c_int,
+    patch: *mut c_int,
+) {
+    let segs = [version.major, version.minor, version.patch];
+    for (ptr, seg) in [major, minor, patch].into_iter().zip(segs) {
+        if !ptr.is_null() {
+            // SAFETY: the caller guarantees the pointer is valid.
+            unsafe { *ptr = c_int::try_from(seg).unwrap_or(c_int::MAX) };
+        }
+    }
+}
+
+/// Finds the first EditorConfig file (from the root) with a malformed line or
+/// a section whose glob can't be compiled, and the first such line in it.
+fn find_parse_error(
+    path: &Path,
+    options: &Options,
+) -> Option<(PathBuf, usize)> {
+    let behavior = Behavior::of(options.version);
+
+    for dir in ancestors(path) {
+        let Ok(Some(document)) = read_document(dir, options) else { continue };
+        let normalized_dir = normalize_path(dir).ok()?;
+
+        let error = document.lines.iter().find(|line| match &line.kind {
+            LineKind::Invalid => true,
+            LineKind::Section { .. } => line.pattern().is_some_and(|pattern| {
+                Glob::with_behavior(&normalized_dir, pattern, &behavior)
+                    .is_err()
+            }),
+            _ => false,
+        });
+        if let Some(line) = error {
+            return Some((dir.join(options.file_name), line.number));
+        }
+    }
+    None
+}
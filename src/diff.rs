@@ -0,0 +1,77 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    ConfigSource, Error, Properties, TreeOptions, resolve_tree_from_source,
+};
+
+/// A property which differs between two resolutions of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyChange {
+    pub key: String,
+    /// The old value (`None` if the property wasn't set).
+    pub old: Option<String>,
+    /// The new value (`None` if the property isn't set anymore).
+    pub new: Option<String>,
+}
+
+/// A file and the changes in its properties (or the reason they couldn't be
+/// resolved, by either set of EditorConfig files).
+pub type FileDiff = (PathBuf, Result<Vec<PropertyChange>, Error>);
+
+/// The changes from the `old` properties to the `new` ones, sorted by key.
+pub fn diff_properties(
+    old: &Properties,
+    new: &Properties,
+) -> Vec<PropertyChange> {
+    let keys: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+
+    keys.into_iter()
+        .filter(|&key| old.get(key) != new.get(key))
+        .map(|key| PropertyChange {
+            key: key.clone(),
+            old: old.get(key).cloned(),
+            new: new.get(key).cloned(),
+        })
+        .collect()
+}
+
+/// Resolves every file under `root` against both the `old` and the `new`
+/// EditorConfig files, and lists the files whose properties differ (or
+/// couldn't be resolved).
+///
+/// The files are those of [`resolve_tree`](crate::resolve_tree), which is
+/// used for both resolutions; e.g., with a [`Mirror`](crate::Mirror) of an
+/// older copy of the EditorConfig files as `old`, and
+/// [`FileSystem`](crate::FileSystem) as `new`.
+pub fn diff_tree<P, O, N>(
+    root: P,
+    old: &O,
+    new: &N,
+    options: TreeOptions,
+) -> Result<Vec<FileDiff>, Error>
+where
+    P: AsRef<Path>,
+    O: ConfigSource + ?Sized,
+    N: ConfigSource + ?Sized,
+{
+    let root = root.as_ref();
+    let old = resolve_tree_from_source(root, options, old)?;
+    let new = resolve_tree_from_source(root, options, new)?;
+
+    let mut new: HashMap<_, _> = new.into_iter().collect();
+
+    // Both walk the tree, so a file only misses from one if it was created or
+    // deleted in between.
+    let diffs = old.into_iter().filter_map(|(path, old)| {
+        match (old, new.remove(&path)?) {
+            (Ok(old), Ok(new)) => {
+                let changes = diff_properties(&old, &new);
+                (!changes.is_empty()).then_some((path, Ok(changes)))
+            }
+            (Err(e), _) | (_, Err(e)) => Some((path, Err(e))),
+        }
+    });
+
+    Ok(diffs.collect())
+}
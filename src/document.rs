@@ -0,0 +1,219 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::Behavior;
+
+const COMMENT: &[char] = &['#', ';'];
+
+/// An EditorConfig file, parsed line by line.
+///
+/// Every line of the original file is kept (including blank, comment and
+/// invalid lines), so tools can point back into the file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Document {
+    pub lines: Vec<Line>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line {
+    /// The 1-based line number.
+    pub number: usize,
+    /// The line, without the line terminator.
+    pub text: String,
+    pub kind: LineKind,
+}
+
+/// The ranges are byte offsets into [`Line::text`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum LineKind {
+    Blank,
+    Comment,
+    /// A section header, with the range of the glob between the brackets.
+    Section {
+        pattern: Range<usize>,
+    },
+    /// A `key = value` pair, with the ranges of the (trimmed) key and value.
+    Pair {
+        key: Range<usize>,
+        value: Range<usize>,
+    },
+    /// A line which is neither of the above, and is ignored.
+    Invalid,
+}
+
+/// A view of a section, i.e., a section header and the lines following it up
+/// to the next header. The preamble is the section with no header.
+#[derive(Debug, Clone, Copy)]
+pub struct Section<'a> {
+    pub header: Option<&'a Line>,
+    pub lines: &'a [Line],
+}
+
+impl Document {
+    /// Parses `contents` as of [`MAX_VERSION`](crate::MAX_VERSION).
+    pub fn parse(contents: &str) -> Self {
+        Self::parse_with_behavior(contents, &Behavior::default())
+    }
+
+    /// Parses `contents` as of `behavior`, i.e., with the comments after
+    /// section headers and the `:` separators of older versions, if it has
+    /// them.
+    pub fn parse_with_behavior(contents: &str, behavior: &Behavior) -> Self {
+        let lines = contents
+            .lines()
+            .enumerate()
+            .map(|(idx, text)| Line::parse(idx + 1, text, behavior))
+            .collect();
+        Self { lines }
+    }
+
+    /// The preamble, followed by all the sections, in order.
+    pub fn sections(&self) -> impl Iterator<Item = Section<'_>> {
+        let mut rest = self.lines.as_slice();
+        let mut header = None;
+        let mut done = false;
+
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+
+            let end = rest
+                .iter()
+                .position(|l| matches!(l.kind, LineKind::Section { .. }))
+                .unwrap_or(rest.len());
+            let section = Section { header, lines: &rest[..end] };
+
+            if let Some((next, tail)) = rest[end..].split_first() {
+                header = Some(next);
+                rest = tail;
+            } else {
+                done = true;
+            }
+
+            Some(section)
+        })
+    }
+
+    /// Whether the preamble contains `root = true`.
+    pub fn is_root(&self) -> bool {
+        self.root_line().is_some()
+    }
+
+    /// The line at which the preamble sets `root = true`.
+    pub fn root_line(&self) -> Option<&Line> {
+        self.sections()
+            .next()?
+            .pairs()
+            .find(|(_line, key, value)| {
+                key.eq_ignore_ascii_case("root")
+                    && value.eq_ignore_ascii_case("true")
+            })
+            .map(|(line, _key, _value)| line)
+    }
+}
+
+impl Line {
+    fn parse(number: usize, text: &str, behavior: &Behavior) -> Self {
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        let trimmed = text.trim();
+        let offset = text.len() - text.trim_start().len();
+        let separators: &[char] =
+            if behavior.colon_separators { &['=', ':'] } else { &['='] };
+
+        let kind = if trimmed.is_empty() {
+            LineKind::Blank
+        } else if trimmed.starts_with(COMMENT) {
+            LineKind::Comment
+        } else if let Some(end) = section_end(trimmed, behavior) {
+            LineKind::Section { pattern: offset + 1..offset + end }
+        } else if let Some((key, value)) = trimmed.split_once(separators)
+            && !key.trim().is_empty()
+        {
+            let key_start = offset;
+            let key_end = key_start + key.trim_end().len();
+            let value_start = offset
+                + key.len()
+                + 1
+                + (value.len() - value.trim_start().len());
+            let value_end = value_start + value.trim().len();
+            LineKind::Pair {
+                key: key_start..key_end,
+                value: value_start..value_end,
+            }
+        } else {
+            LineKind::Invalid
+        };
+
+        Self { number, text: text.to_owned(), kind }
+    }
+
+    /// The glob of a section header.
+    pub fn pattern(&self) -> Option<&str> {
+        match &self.kind {
+            LineKind::Section { pattern } => Some(&self.text[pattern.clone()]),
+            _ => None,
+        }
+    }
+
+    /// The key and value of a pair, both trimmed and in their original form.
+    pub fn pair(&self) -> Option<(&str, &str)> {
+        match &self.kind {
+            LineKind::Pair { key, value } => {
+                Some((&self.text[key.clone()], &self.text[value.clone()]))
+            }
+            _ => None,
+        }
+    }
+
+    /// The value of a pair, trimmed and in its original form (i.e., with its
+    /// inline comment and escapes, if any).
+    pub fn raw_value(&self) -> Option<&str> {
+        self.pair().map(|(_key, value)| value)
+    }
+
+    /// The value of a pair, as read with `behavior` (see [`Behavior::value`]).
+    pub fn value(&self, behavior: &Behavior) -> Option<Cow<'_, str>> {
+        self.raw_value().map(|value| behavior.value(value))
+    }
+}
+
+/// The index of the `]` closing the section header `trimmed`, if it is one.
+///
+/// With inline comments, the header may be followed by a comment.
+fn section_end(trimmed: &str, behavior: &Behavior) -> Option<usize> {
+    if !trimmed.starts_with('[') {
+        return None;
+    }
+    if trimmed.ends_with(']') {
+        return Some(trimmed.len() - 1);
+    }
+
+    if !behavior.inline_comments {
+        return None;
+    }
+    trimmed
+        .match_indices(']')
+        .map(|(idx, _)| idx)
+        .find(|&idx| trimmed[idx + 1..].trim_start().starts_with(COMMENT))
+}
+
+impl<'a> Section<'a> {
+    /// The glob of the section, or `None` for the preamble.
+    pub fn pattern(&self) -> Option<&'a str> {
+        self.header.and_then(Line::pattern)
+    }
+
+    pub fn pairs(&self) -> impl Iterator<Item = (&'a Line, &'a str, &'a str)> {
+        self.lines.iter().filter_map(|line| {
+            line.pair().map(|(key, value)| (line, key, value))
+        })
+    }
+}
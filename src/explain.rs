@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    Behavior, Document, Error, Options, Properties, ancestors,
+    finish_properties, normalize_pair, normalize_path, read_document,
+    section_matches,
+};
+
+/// A trace of how the properties of a file were resolved.
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    /// Every EditorConfig file consulted, from the root to the leaf.
+    pub files: Vec<ConfigFile>,
+    /// The final properties, as returned by
+    /// [`properties_with_options`](crate::properties_with_options).
+    pub properties: Properties,
+    /// Where each of the final properties came from.
+    pub origins: HashMap<String, Origin>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// Set by a pair at the (1-based) `line` of the EditorConfig file at
+    /// `path`.
+    Pair { path: PathBuf, line: usize },
+    /// Derived from other properties (e.g., `tab_width` from `indent_size`).
+    Derived,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    pub path: PathBuf,
+    pub document: Document,
+    /// The line at which `root = true` is set, if this file is a root.
+    pub root: Option<usize>,
+    /// Whether this file was discarded because a file closer to the target is
+    /// a root.
+    pub discarded: bool,
+    /// All the sections of this file (excluding the preamble).
+    pub sections: Vec<SectionTrace>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SectionTrace {
+    /// The line of the section header.
+    pub line: usize,
+    pub pattern: String,
+    pub is_match: bool,
+    /// The pairs the section contributed (empty unless it matched).
+    pub pairs: Vec<PairTrace>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PairTrace {
+    pub line: usize,
+    /// The key and value as inserted into the properties (i.e., normalized).
+    pub key: String,
+    pub value: String,
+    /// What replaced this value later on, if anything did.
+    pub overridden_by: Option<Origin>,
+}
+
+/// Explains how the properties for the file at `path` are resolved.
+pub fn explain<P>(path: P) -> Result<Explanation, Error>
+where
+    P: AsRef<Path>,
+{
+    explain_with_options(path, Options::default())
+}
+
+pub fn explain_with_options<P>(
+    path: P,
+    options: Options,
+) -> Result<Explanation, Error>
+where
+    P: AsRef<Path>,
+{
+    // The indices of the file, the section and the pair which set each key.
+    type PairIdx = (usize, usize, usize);
+
+    let normalized_path = normalize_path(path.as_ref())?;
+    let behavior = Behavior::of(options.version);
+
+    let mut files: Vec<ConfigFile> = vec![];
+    let mut properties = HashMap::new();
+    let mut setters: HashMap<String, PairIdx> = HashMap::new();
+
+    for dir in ancestors(path.as_ref()) {
+        let Some(document) = read_document(dir, &options)? else { continue };
+        let normalized_ec_dir = normalize_path(dir)?;
+
+        let root = document.root_line().map(|l| l.number);
+        if root.is_some() {
+            properties.clear();
+            setters.clear();
+            files.iter_mut().for_each(|f| f.discarded = true);
+        }
+
+        let mut sections = vec![];
+
+        for section in document.sections() {
+            let Some(header) = section.header else { continue };
+            let pattern = section.pattern().unwrap_or_default();
+            let is_match = behavior.accepts_section(pattern)
+                && section_matches(
+                    &section,
+                    &normalized_ec_dir,
+                    &normalized_path,
+                    &behavior,
+                )?;
+
+            let pairs = section
+                .pairs()
+                .filter(|&(_line, key, value)| {
+                    is_match
+                        && behavior.accepts_pair(key, &behavior.value(value))
+                })
+                .map(|(line, key, value)| {
+                    let (key, value) = normalize_pair(key, value, &behavior);
+                    PairTrace {
+                        line: line.number,
+                        key,
+                        value,
+                        overridden_by: None,
+                    }
+                })
+                .collect();
+
+            sections.push(SectionTrace {
+                line: header.number,
+                pattern: pattern.to_owned(),
+                is_match,
+                pairs,
+            });
+        }
+
+        files.push(ConfigFile {
+            path: dir.join(options.file_name),
+            document,
+            root,
+            discarded: false,
+            sections,
+        });
+
+        // Apply the pairs in order, marking the ones they override.
+        let f = files.len() - 1;
+        for s in 0..files[f].sections.len() {
+            for p in 0..files[f].sections[s].pairs.len() {
+                let PairTrace { line, key, value, .. } =
+                    files[f].sections[s].pairs[p].clone();
+
+                if let Some((pf, ps, pp)) =
+                    setters.insert(key.clone(), (f, s, p))
+                {
+                    let path = files[f].path.clone();
+                    files[pf].sections[ps].pairs[pp].overridden_by =
+                        Some(Origin::Pair { path, line });
+                }
+
+                properties.insert(key, value);
+            }
+        }
+    }
+
+    let before = properties.clone();
+    finish_properties(&mut properties, &options);
+
+    let mut origins = HashMap::new();
+    for (key, value) in properties.iter() {
+        let set_by_pair = before.get(key) == Some(value);
+        let origin = match setters.get(key) {
+            Some(&(f, s, p)) if set_by_pair => {
+                let file = &files[f];
+                let line = file.sections[s].pairs[p].line;
+                Origin::Pair { path: file.path.clone(), line }
+            }
+            Some(&(f, s, p)) => {
+                files[f].sections[s].pairs[p].overridden_by =
+                    Some(Origin::Derived);
+                Origin::Derived
+            }
+            None => Origin::Derived,
+        };
+        origins.insert(key.clone(), origin);
+    }
+
+    Ok(Explanation { files, properties, origins })
+}
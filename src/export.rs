@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use crate::{Properties, Value, typed_properties};
+
+/// The resolved properties, typed, with the accessors the converters share.
+struct Typed(HashMap<String, Value>);
+
+impl Typed {
+    fn new(properties: &Properties) -> Self {
+        Self(typed_properties(properties))
+    }
+
+    fn keyword(&self, key: &str) -> Option<&str> {
+        match self.0.get(key) {
+            Some(Value::Keyword(keyword)) => Some(keyword),
+            _ => None,
+        }
+    }
+
+    fn integer(&self, key: &str) -> Option<u32> {
+        match self.0.get(key) {
+            Some(&Value::Integer(n)) => Some(n),
+            _ => None,
+        }
+    }
+
+    fn flag(&self, key: &str) -> Option<bool> {
+        self.keyword(key).map(|keyword| keyword == "true")
+    }
+
+    fn use_tabs(&self) -> Option<bool> {
+        self.keyword("indent_style").map(|style| style == "tab")
+    }
+
+    /// The width of an indentation level (`indent_size = tab` is resolved to
+    /// `tab_width` already, if it's set).
+    fn indent_width(&self) -> Option<u32> {
+        self.integer("indent_size").or_else(|| self.integer("tab_width"))
+    }
+
+    fn tab_width(&self) -> Option<u32> {
+        self.integer("tab_width").or_else(|| self.integer("indent_size"))
+    }
+}
+
+/// Converts the resolved `properties` of a file to the options of a
+/// `rustfmt.toml`.
+///
+/// The properties which rustfmt has no option for (or which are unset) are
+/// left out, e.g., `end_of_line = cr`.
+pub fn rustfmt_config(properties: &Properties) -> String {
+    let typed = Typed::new(properties);
+
+    let newline_style = match typed.keyword("end_of_line") {
+        Some("lf") => Some("\"Unix\""),
+        Some("crlf") => Some("\"Windows\""),
+        _ => None,
+    };
+
+    let options = [
+        ("hard_tabs", typed.use_tabs().map(|tabs| tabs.to_string())),
+        ("tab_spaces", typed.indent_width().map(|n| n.to_string())),
+        (
+            "max_width",
+            typed.integer("max_line_length").map(|n| n.to_string()),
+        ),
+        ("newline_style", newline_style.map(str::to_owned)),
+    ];
+
+    let mut config = String::new();
+    for (key, value) in present(options) {
+        config.push_str(&format!("{key} = {value}\n"));
+    }
+    config
+}
+
+/// Converts the resolved `properties` of a file to Prettier options (as the
+/// JSON of a `.prettierrc`).
+pub fn prettier_config(properties: &Properties) -> String {
+    let typed = Typed::new(properties);
+
+    let options = [
+        ("useTabs", typed.use_tabs().map(|tabs| tabs.to_string())),
+        ("tabWidth", typed.indent_width().map(|n| n.to_string())),
+        (
+            "printWidth",
+            typed.integer("max_line_length").map(|n| n.to_string()),
+        ),
+        ("endOfLine", typed.keyword("end_of_line").map(json_string)),
+    ];
+
+    json_object(&present(options), 0) + "\n"
+}
+
+/// Converts the resolved `properties` of a file to the keys of a
+/// `.clang-format`.
+///
+/// Tabs are only used for indentation (`UseTab: ForIndentation`), and
+/// `max_line_length = off` lifts the limit (`ColumnLimit: 0`). `LineEnding`
+/// and `InsertNewlineAtEOF` need clang-format 16.
+pub fn clang_format_config(properties: &Properties) -> String {
+    let typed = Typed::new(properties);
+
+    let use_tab = typed
+        .use_tabs()
+        .map(|tabs| if tabs { "ForIndentation" } else { "Never" }.to_owned());
+    let column_limit = match typed.0.get("max_line_length") {
+        Some(&Value::Integer(n)) => Some(n.to_string()),
+        Some(Value::Keyword(_off)) => Some("0".to_owned()),
+        _ => None,
+    };
+    let line_ending = match typed.keyword("end_of_line") {
+        Some("lf") => Some("LF".to_owned()),
+        Some("crlf") => Some("CRLF".to_owned()),
+        _ => None,
+    };
+    // clang-format can add the final newline, but not remove it.
+    let insert_final_newline = match typed.flag("insert_final_newline") {
+        Some(true) => Some("true".to_owned()),
+        _ => None,
+    };
+
+    let keys = [
+        ("UseTab", use_tab),
+        ("IndentWidth", typed.indent_width().map(|n| n.to_string())),
+        ("TabWidth", typed.tab_width().map(|n| n.to_string())),
+        ("ColumnLimit", column_limit),
+        ("LineEnding", line_ending),
+        ("InsertNewlineAtEOF", insert_final_newline),
+    ];
+
+    let mut config = String::new();
+    for (key, value) in present(keys) {
+        config.push_str(&format!("{key}: {value}\n"));
+    }
+    config
+}
+
+/// Converts the resolved properties of a file of each language (by its VS Code
+/// language ID, e.g. `rust`) to the language overrides of a VS Code
+/// `settings.json`.
+pub fn vscode_settings<'a, I>(languages: I) -> String
+where
+    I: IntoIterator<Item = (&'a str, &'a Properties)>,
+{
+    let overrides: Vec<_> = languages
+        .into_iter()
+        .map(|(language, properties)| {
+            let settings = vscode_language_settings(&Typed::new(properties));
+            (format!("[{language}]"), json_object(&settings, 1))
+        })
+        .collect();
+
+    json_object(&overrides, 0) + "\n"
+}
+
+fn vscode_language_settings(typed: &Typed) -> Vec<(&'static str, String)> {
+    let eol = match typed.keyword("end_of_line") {
+        Some("lf") => Some(json_string("\n")),
+        Some("crlf") => Some(json_string("\r\n")),
+        _ => None,
+    };
+    let encoding = match typed.keyword("charset") {
+        Some("latin1") => Some("iso88591"),
+        Some("utf-8") => Some("utf8"),
+        Some("utf-8-bom") => Some("utf8bom"),
+        Some("utf-16be") => Some("utf16be"),
+        Some("utf-16le") => Some("utf16le"),
+        _ => None,
+    };
+
+    let settings = [
+        (
+            "editor.insertSpaces",
+            typed.use_tabs().map(|tabs| (!tabs).to_string()),
+        ),
+        ("editor.tabSize", typed.tab_width().map(|n| n.to_string())),
+        (
+            "editor.indentSize",
+            typed.indent_width().map(|n| n.to_string()),
+        ),
+        (
+            "editor.rulers",
+            typed.integer("max_line_length").map(|n| format!("[{n}]")),
+        ),
+        ("files.eol", eol),
+        ("files.encoding", encoding.map(json_string)),
+        (
+            "files.insertFinalNewline",
+            typed.flag("insert_final_newline").map(|flag| flag.to_string()),
+        ),
+        (
+            "files.trimTrailingWhitespace",
+            typed.flag("trim_trailing_whitespace").map(|flag| flag.to_string()),
+        ),
+    ];
+
+    present(settings)
+}
+
+/// The entries which have a value.
+fn present<const N: usize>(
+    entries: [(&'static str, Option<String>); N],
+) -> Vec<(&'static str, String)> {
+    entries
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key, value)))
+        .collect()
+}
+
+/// A JSON object of the (already JSON) values, indented `level` levels deep
+/// (but for the first line).
+fn json_object<K>(entries: &[(K, String)], level: usize) -> String
+where
+    K: AsRef<str>,
+{
+    if entries.is_empty() {
+        return "{}".to_owned();
+    }
+
+    let indent = "  ".repeat(level);
+    let members: Vec<_> = entries
+        .iter()
+        .map(|(key, value)| {
+            format!("{indent}  {}: {value}", json_string(key.as_ref()))
+        })
+        .collect();
+    format!("{{\n{}\n{indent}}}", members.join(",\n"))
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            ch if ch.is_control() => {
+                json.push_str(&format!("\\u{:04x}", u32::from(ch)));
+            }
+            ch => json.push(ch),
+        }
+    }
+    json.push('"');
+    json
+}
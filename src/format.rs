@@ -0,0 +1,222 @@
+use crate::property::KnownProperty;
+use crate::{Document, Line, LineKind};
+
+/// The options of [`format()`], built from [`FormatOptions::default`] and the
+/// `with_*` methods (the struct is `#[non_exhaustive]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FormatOptions {
+    /// Sort the pairs of each section by key (defaults to `false`).
+    ///
+    /// Pairs are only sorted among the pairs around them, i.e., a comment
+    /// which isn't attached to a pair stays in place, and splits the section
+    /// into separately sorted groups.
+    pub sort_keys: bool,
+    /// The marker to start comments with, `#` or `;` (defaults to `#`).
+    pub comment_marker: char,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self { sort_keys: false, comment_marker: '#' }
+    }
+}
+
+impl FormatOptions {
+    /// Sets [`FormatOptions::sort_keys`].
+    pub fn with_sort_keys(self, sort_keys: bool) -> Self {
+        Self { sort_keys, ..self }
+    }
+
+    /// Sets [`FormatOptions::comment_marker`].
+    pub fn with_comment_marker(self, comment_marker: char) -> Self {
+        Self { comment_marker, ..self }
+    }
+}
+
+/// A pair (or an invalid line) and the comments directly above it, or a block
+/// of comments which isn't attached to anything.
+#[derive(Debug)]
+struct Item {
+    comments: Vec<String>,
+    line: Option<String>,
+    /// The (lowercased) key, for sorting.
+    key: Option<String>,
+    blank_before: bool,
+}
+
+#[derive(Debug)]
+struct FormattedSection {
+    /// The comments directly above the header.
+    comments: Vec<String>,
+    header: Option<String>,
+    items: Vec<Item>,
+}
+
+/// Formats the contents of an EditorConfig file canonically.
+///
+/// - Pairs are written as `key = value`, with the keys lowercased, and so are
+///   the values of known properties which are keywords (e.g., `TRUE`).
+/// - Comments use the same marker, and stay attached to the line below them.
+/// - Sections are separated by a single blank line, and runs of blank lines are
+///   collapsed.
+/// - Leading and trailing whitespace is removed, and the file ends with a
+///   newline.
+pub fn format(contents: &str, options: FormatOptions) -> String {
+    let document = Document::parse(contents);
+
+    let mut sections: Vec<FormattedSection> = vec![];
+    // A block of comments at the end of a section, directly above the next
+    // header, belongs to that header.
+    let mut trailing: Option<Item> = None;
+
+    for section in document.sections() {
+        let comments = trailing.take().map(|i| i.comments).unwrap_or_default();
+
+        let mut items;
+        (items, trailing) = group_items(section.lines, &options);
+
+        if options.sort_keys {
+            sort_items(&mut items);
+        }
+
+        sections.push(FormattedSection {
+            comments,
+            header: section.header.map(|h| format_line(h, &options)),
+            items,
+        });
+    }
+
+    // The comments at the end of the file have no header to belong to.
+    if let Some(section) = sections.last_mut() {
+        section.items.extend(trailing);
+    }
+
+    let mut out = String::with_capacity(contents.len());
+
+    for section in sections.iter() {
+        if let Some(header) = section.header.as_ref() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            section.comments.iter().for_each(|c| push_line(&mut out, c));
+            push_line(&mut out, header);
+        }
+
+        for (idx, item) in section.items.iter().enumerate() {
+            if item.blank_before && 0 < idx {
+                out.push('\n');
+            }
+            item.comments.iter().for_each(|c| push_line(&mut out, c));
+            if let Some(line) = item.line.as_ref() {
+                push_line(&mut out, line);
+            }
+        }
+    }
+
+    out
+}
+
+/// Groups the lines into items, except for the comments at the end which
+/// aren't followed by a blank line, which are returned separately.
+fn group_items(
+    lines: &[Line],
+    options: &FormatOptions,
+) -> (Vec<Item>, Option<Item>) {
+    let mut items = vec![];
+    let mut comments = vec![];
+    let mut blank_before = false;
+
+    for line in lines {
+        match &line.kind {
+            LineKind::Blank => {
+                if !comments.is_empty() {
+                    items.push(Item {
+                        comments: std::mem::take(&mut comments),
+                        line: None,
+                        key: None,
+                        blank_before,
+                    });
+                    blank_before = false;
+                }
+                blank_before |= !items.is_empty();
+            }
+            LineKind::Comment => {
+                comments.push(format_line(line, options));
+            }
+            LineKind::Pair { .. }
+            | LineKind::Invalid
+            | LineKind::Section { .. } => {
+                items.push(Item {
+                    comments: std::mem::take(&mut comments),
+                    line: Some(format_line(line, options)),
+                    key: line.pair().map(|(key, _value)| key.to_lowercase()),
+                    blank_before,
+                });
+                blank_before = false;
+            }
+        }
+    }
+
+    let trailing = (!comments.is_empty()).then_some(Item {
+        comments,
+        line: None,
+        key: None,
+        blank_before,
+    });
+
+    (items, trailing)
+}
+
+/// Sorts each run of pairs (the runs are split by anything else).
+fn sort_items(items: &mut [Item]) {
+    for run in items.split_mut(|item| item.key.is_none()) {
+        let Some(blank_before) = run.first().map(|item| item.blank_before)
+        else {
+            continue;
+        };
+
+        run.sort_by(|a, b| a.key.cmp(&b.key));
+        run.iter_mut().for_each(|item| item.blank_before = false);
+        run[0].blank_before = blank_before;
+    }
+}
+
+fn format_line(line: &Line, options: &FormatOptions) -> String {
+    let trimmed = line.text.trim();
+
+    match &line.kind {
+        LineKind::Blank => String::new(),
+        LineKind::Comment => {
+            let mut chars = trimmed.chars();
+            chars.next();
+            format!("{}{}", options.comment_marker, chars.as_str())
+        }
+        LineKind::Section { .. } | LineKind::Invalid => trimmed.to_owned(),
+        LineKind::Pair { .. } => {
+            let (key, value) = line.pair().unwrap_or_default();
+            let key = key.to_lowercase();
+            let value = normalize_value(&key, value);
+            if value.is_empty() {
+                format!("{key} =")
+            } else {
+                format!("{key} = {value}")
+            }
+        }
+    }
+}
+
+/// Lowercases the value if it's a keyword of a known property (or `unset`).
+fn normalize_value(key: &str, value: &str) -> String {
+    let is_keyword = value.eq_ignore_ascii_case("unset")
+        || KnownProperty::get(key).is_some_and(|p| {
+            p.keywords().any(|k| k.eq_ignore_ascii_case(value))
+        });
+
+    if is_keyword { value.to_lowercase() } else { value.to_owned() }
+}
+
+fn push_line(out: &mut String, line: &str) {
+    out.push_str(line);
+    out.push('\n');
+}
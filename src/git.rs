@@ -0,0 +1,81 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use git2::{ErrorCode, ObjectType, Repository, Tree};
+
+use crate::ConfigSource;
+
+/// The EditorConfig files of a commit (or any tree) in a Git repository, read
+/// from its object database, without checking it out.
+///
+/// The tree is taken to be at `root`, so the paths under `root` are looked up
+/// in it. The EditorConfig files outside of `root` aren't part of the tree,
+/// and are considered missing (resolving doesn't depend on the machine).
+pub struct GitTree<'repo> {
+    repo: &'repo Repository,
+    tree: Tree<'repo>,
+    root: PathBuf,
+}
+
+impl<'repo> GitTree<'repo> {
+    /// The tree of the commit `rev` refers to (e.g., `HEAD`, a branch or a
+    /// commit ID), at the working directory of `repo`.
+    ///
+    /// A bare repository has no working directory, so its trees must be put
+    /// somewhere with [`GitTree::with_root`].
+    pub fn new(
+        repo: &'repo Repository,
+        rev: &str,
+    ) -> Result<Self, git2::Error> {
+        let root = repo.workdir().ok_or_else(|| {
+            git2::Error::from_str("a bare repository has no working directory")
+        })?;
+        let tree = repo.revparse_single(rev)?.peel_to_tree()?;
+        Ok(Self::with_root(repo, tree, root))
+    }
+
+    /// The `tree` of `repo`, at `root` (which should be absolute, as are the
+    /// paths which are looked up).
+    pub fn with_root<P>(
+        repo: &'repo Repository,
+        tree: Tree<'repo>,
+        root: P,
+    ) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let root = root.as_ref().to_path_buf();
+        Self { repo, tree, root }
+    }
+
+    pub fn tree(&self) -> &Tree<'repo> {
+        &self.tree
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl ConfigSource for GitTree<'_> {
+    fn read(&self, path: &Path) -> io::Result<Option<String>> {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return Ok(None);
+        };
+
+        let entry = match self.tree.get_path(relative) {
+            Ok(entry) => entry,
+            Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(io::Error::other(e)),
+        };
+        // E.g., a directory named like an EditorConfig file.
+        if entry.kind() != Some(ObjectType::Blob) {
+            return Ok(None);
+        }
+
+        let blob = self.repo.find_blob(entry.id()).map_err(io::Error::other)?;
+        String::from_utf8(blob.content().to_vec())
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
@@ -3,7 +3,9 @@ use std::num::{NonZero, NonZeroU32};
 use std::ops::RangeInclusive;
 use std::str::CharIndices;
 
-use regex::{Match, Regex};
+use regex::Regex;
+
+use crate::spec::Behavior;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub(crate) enum Error {
@@ -13,19 +15,39 @@ pub(crate) enum Error {
     RegexCompilation,
 }
 
+#[derive(Debug)]
 pub(crate) struct Glob {
     re: Regex,
-    num_ranges: Vec<RangeInclusive<i32>>,
 }
 
 impl Glob {
+    /// The glob `pattern`, with the semantics of `behavior`.
+    ///
     /// # Arguments
     ///
     /// - `ec_dir` - the directory of the EditorConfig file which contains
     ///   `pattern`. Must be an absolute path which doesn't end with a path
     ///   seperator (i.e., `/`), and must have it's path separators normalized
     ///   to `/`.
-    pub(crate) fn new<P, S>(ec_dir: P, pattern: S) -> Result<Self, Error>
+    pub(crate) fn with_behavior<P, S>(
+        ec_dir: P,
+        pattern: S,
+        behavior: &Behavior,
+    ) -> Result<Self, Error>
+    where
+        P: AsRef<str>,
+        S: AsRef<str>,
+    {
+        Self::from_regex(&Self::regex(ec_dir, pattern, behavior)?)
+    }
+
+    /// Translates the glob into the regex it's matched with (see
+    /// [`Glob::with_behavior`]).
+    pub(crate) fn regex<P, S>(
+        ec_dir: P,
+        pattern: S,
+        behavior: &Behavior,
+    ) -> Result<String, Error>
     where
         P: AsRef<str>,
         S: AsRef<str>,
@@ -36,7 +58,8 @@ impl Glob {
 
         let pattern = pattern.as_ref();
 
-        let (re, num_ranges) = Parser::new(pattern).parse();
+        let (re, num_ranges) =
+            Parser::new(pattern, behavior.numeric_ranges).parse();
 
         if !num_ranges.iter().all(|r| r.start() < r.end()) {
             return Err(Error::InvalidRange);
@@ -54,12 +77,14 @@ impl Glob {
         regex.push_str(&re);
         regex.push('$');
 
-        let re = Regex::new(&regex).map_err(|e| {
-            eprintln!("{e}");
-            Error::RegexCompilation
-        })?;
+        Ok(regex)
+    }
+
+    /// Compiles a regex from [`Glob::regex`].
+    pub(crate) fn from_regex(regex: &str) -> Result<Self, Error> {
+        let re = Regex::new(regex).map_err(|_e| Error::RegexCompilation)?;
 
-        Ok(Self { re, num_ranges })
+        Ok(Self { re })
     }
 
     #[inline]
@@ -67,17 +92,7 @@ impl Glob {
     where
         S: AsRef<str>,
     {
-        fn match_in_range(mat: Match, rng: &RangeInclusive<i32>) -> bool {
-            mat.as_str().parse().is_ok_and(|n| rng.contains(&n))
-        }
-
-        let Some(caps) = self.re.captures(path.as_ref()) else { return false };
-
-        caps.iter()
-            .skip(1)
-            .map(Option::unwrap)
-            .zip(self.num_ranges.iter())
-            .all(|(mat, range)| match_in_range(mat, range))
+        self.re.is_match(path.as_ref())
     }
 }
 
@@ -89,18 +104,21 @@ struct Parser<'a> {
     are_braces_paired: bool,
     brace_level: Option<NonZeroU32>,
 
+    /// Whether `{num1..num2}` is a numeric range (or a literal).
+    allow_num_ranges: bool,
     num_ranges: Vec<RangeInclusive<i32>>,
     regex: String,
 }
 
 impl<'a> Parser<'a> {
-    fn new(pattern: &'a str) -> Self {
+    fn new(pattern: &'a str, allow_num_ranges: bool) -> Self {
         Self {
             pattern,
             chars: pattern.char_indices().peekable(),
             curr: None,
             are_braces_paired: check_are_braces_paired(pattern),
             brace_level: None,
+            allow_num_ranges,
             num_ranges: vec![],
             regex: String::with_capacity(pattern.len()),
         }
@@ -154,7 +172,7 @@ impl<'a> Parser<'a> {
         // If the charset contains a path separator, we treat that charset as a
         // literal - so we need to escape _all_ the meta characters (inc. the
         // bracket delimiters and the internal ranges i.e., `[`, `]`, and `-`).
-        let has_sep = charset_has_path_separator(self.rest().unwrap());
+        let has_sep = self.rest().is_some_and(charset_has_path_separator);
 
         if has_sep {
             self.regex.push('\\');
@@ -206,12 +224,10 @@ impl<'a> Parser<'a> {
         {
             let s = &self.pattern[curr_idx..=curr_idx + closing_brace_offset];
 
-            if let Some(range) = parse_range(s) {
-                // HACK: since we can't validate the number being in a range
-                // using regular expressions (well, at least not in way that
-                // doesn't specify all the possible numbers), we will capture
-                // the number, and validate it against the range after matching.
-                self.regex.push_str("([\\+\\-]?(:?0|[^0]\\d*))");
+            if let Some(range) =
+                parse_range(s).filter(|_range| self.allow_num_ranges)
+            {
+                self.regex.push_str(&range_regex(&range));
                 self.num_ranges.push(range);
             } else {
                 // If the braces only contains one element, we match it
@@ -224,7 +240,7 @@ impl<'a> Parser<'a> {
                 let _ = self.bump().unwrap();
             }
         } else {
-            self.regex.push_str("(:?"); // non-capturing group.
+            self.regex.push_str("(?:"); // non-capturing group.
             self.increase_brace_level();
         }
     }
@@ -252,7 +268,7 @@ impl<'a> Parser<'a> {
         let s = &self.pattern[curr_idx..];
 
         if s.starts_with("/**/") {
-            self.regex.push_str("(:?/|/.*/)");
+            self.regex.push_str("(?:/|/.*/)");
 
             for _ in (0.."/**/".len()).skip(1) {
                 let _ = self.bump().unwrap();
@@ -304,6 +320,75 @@ fn parse_range(s: &str) -> Option<RangeInclusive<i32>> {
     Some(RangeInclusive::new(start, end))
 }
 
+/// A regex matching exactly the integers in `range` (with an optional sign, and
+/// without leading zeros).
+fn range_regex(range: &RangeInclusive<i32>) -> String {
+    let (start, end) = (i64::from(*range.start()), i64::from(*range.end()));
+    let mut alternatives = vec![];
+
+    if start <= 0 && 0 <= end {
+        alternatives.push("[\\+\\-]?0".to_string());
+    }
+    if 1 <= end {
+        let naturals = naturals_regex(start.max(1), end);
+        alternatives.push(format!("\\+?(?:{naturals})"));
+    }
+    if start <= -1 {
+        let naturals = naturals_regex((-end).max(1), -start);
+        alternatives.push(format!("\\-(?:{naturals})"));
+    }
+
+    format!("(?:{})", alternatives.join("|"))
+}
+
+/// A regex matching exactly the numbers from `start` to `end` (both positive).
+fn naturals_regex(start: i64, end: i64) -> String {
+    let mut alternatives = vec![];
+
+    // Split the range by the numbers of digits, so each part is between two
+    // numbers of the same length.
+    let mut low = start;
+    while low <= end {
+        let high = end.min(10_i64.pow(low.ilog10() + 1) - 1);
+        alternatives.push(same_length_regex(
+            low.to_string().as_bytes(),
+            high.to_string().as_bytes(),
+        ));
+        low = high + 1;
+    }
+
+    alternatives.join("|")
+}
+
+/// A regex matching exactly the numbers from `low` to `high`, which have the
+/// same number of digits.
+fn same_length_regex(low: &[u8], high: &[u8]) -> String {
+    let (&l, low_rest) = low.split_first().unwrap();
+    let (&h, high_rest) = high.split_first().unwrap();
+
+    if low_rest.is_empty() {
+        let (l, h) = (char::from(l), char::from(h));
+        return if l == h { l.to_string() } else { format!("[{l}-{h}]") };
+    }
+    if l == h {
+        let rest = same_length_regex(low_rest, high_rest);
+        return format!("{}(?:{rest})", char::from(l));
+    }
+
+    let n = low_rest.len();
+    let low_tail = same_length_regex(low_rest, &vec![b'9'; n]);
+    let high_tail = same_length_regex(&vec![b'0'; n], high_rest);
+
+    let mut alternatives = vec![format!("{}(?:{low_tail})", char::from(l))];
+    if l + 1 < h {
+        let (l, h) = (char::from(l + 1), char::from(h - 1));
+        alternatives.push(format!("[{l}-{h}][0-9]{{{n}}}"));
+    }
+    alternatives.push(format!("{}(?:{high_tail})", char::from(h)));
+
+    alternatives.join("|")
+}
+
 fn charset_has_path_separator(s: &str) -> bool {
     let mut escaped = false;
 
@@ -320,15 +405,21 @@ fn charset_has_path_separator(s: &str) -> bool {
     false
 }
 
+/// If `s` starts with braces which contain a single item, the offset of their
+/// closing brace.
 fn is_single_item_braces(s: &str) -> Option<usize> {
     let mut escaped = false;
+    // The nesting level within the braces.
+    let mut level = 0_usize;
 
-    for (idx, byte) in s.bytes().enumerate() {
+    for (idx, byte) in s.bytes().enumerate().skip(1) {
         match byte {
             _ if escaped => escaped = false,
             b'\\' => escaped = true,
-            b'}' => return Some(idx),
-            b',' => return None,
+            b'{' => level += 1,
+            b'}' if level == 0 => return Some(idx),
+            b'}' => level -= 1,
+            b',' if level == 0 => return None,
             _ => {}
         }
     }
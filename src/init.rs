@@ -0,0 +1,231 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::{Error, Properties, walk};
+
+/// Files larger than this are skipped when inferring conventions.
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// The properties [`infer`] may infer, in the order they're written.
+const KEYS: &[&str] = &[
+    "indent_style",
+    "indent_size",
+    "end_of_line",
+    "charset",
+    "trim_trailing_whitespace",
+    "insert_final_newline",
+];
+
+/// The votes of the files for each value of each property.
+type Votes = HashMap<&'static str, HashMap<String, usize>>;
+
+/// The files without an extension which are worth a section of their own.
+const KNOWN_FILE_NAMES: &[&str] = &[
+    "Makefile",
+    "GNUmakefile",
+    "makefile",
+    "Justfile",
+    "justfile",
+    "Dockerfile",
+    "Containerfile",
+    "Gemfile",
+    "Rakefile",
+    "Vagrantfile",
+];
+
+/// Infers the dominant conventions of the files under `root`, per file
+/// extension (or per file name, for well-known files without an extension,
+/// e.g., `Makefile`).
+///
+/// Hidden files, ignored files (e.g., by `.gitignore`), binary files, large
+/// files and files which can't be read are skipped. A value is only inferred
+/// if the majority of the files (with the extension) that have an opinion
+/// about it agree on it.
+pub fn infer<P>(root: P) -> Result<BTreeMap<String, Properties>, Error>
+where
+    P: AsRef<Path>,
+{
+    let files = walk::unignored_files(root.as_ref()).map_err(Error::Io)?;
+
+    let mut votes: BTreeMap<String, Votes> = BTreeMap::new();
+
+    for file in files {
+        let Some(pattern) = pattern(&file) else { continue };
+        if fs::metadata(&file).is_ok_and(|m| m.len() > MAX_FILE_SIZE) {
+            continue;
+        }
+        let Ok(contents) = fs::read(&file) else { continue };
+        if contents.is_empty() || contents.contains(&0) {
+            // Empty or binary.
+            continue;
+        }
+
+        let file_votes = votes.entry(pattern).or_default();
+        for (key, value) in detect(&contents) {
+            *file_votes.entry(key).or_default().entry(value).or_default() += 1;
+        }
+    }
+
+    Ok(votes
+        .into_iter()
+        .map(|(pattern, votes)| (pattern, elect(votes)))
+        .filter(|(_pattern, properties)| !properties.is_empty())
+        .collect())
+}
+
+/// Renders the inferred conventions (see [`infer`]) as a root EditorConfig
+/// file, with a `[*]` section for the pairs all the sections agree on.
+pub fn scaffold(conventions: &BTreeMap<String, Properties>) -> String {
+    let mut common: Vec<(&str, &str)> = vec![];
+    if let Some(first) = conventions.values().next() {
+        for &key in KEYS {
+            if let Some(value) = first.get(key)
+                && conventions.values().all(|p| p.get(key) == Some(value))
+            {
+                common.push((key, value));
+            }
+        }
+    }
+
+    let mut out = String::from("root = true\n");
+
+    if !common.is_empty() && 1 < conventions.len() {
+        out.push_str("\n[*]\n");
+        for (key, value) in common.iter() {
+            out.push_str(&format!("{key} = {value}\n"));
+        }
+    } else {
+        common.clear();
+    }
+
+    for (pattern, properties) in conventions.iter() {
+        let pairs: Vec<_> = KEYS
+            .iter()
+            .filter(|key| !common.iter().any(|(k, _v)| k == *key))
+            .filter_map(|&key| properties.get(key).map(|v| (key, v)))
+            .collect();
+        if pairs.is_empty() {
+            continue;
+        }
+
+        out.push_str(&format!("\n[{pattern}]\n"));
+        for (key, value) in pairs {
+            out.push_str(&format!("{key} = {value}\n"));
+        }
+    }
+
+    out
+}
+
+/// The section glob for the files like `file`.
+fn pattern(file: &Path) -> Option<String> {
+    const META: &[char] = &['*', '?', '[', ']', '{', '}', ',', '\\', '#', ';'];
+
+    let escape = |s: &str| {
+        s.chars().fold(String::new(), |mut acc, ch| {
+            if META.contains(&ch) {
+                acc.push('\\');
+            }
+            acc.push(ch);
+            acc
+        })
+    };
+
+    match file.extension() {
+        Some(ext) => Some(format!("*.{}", escape(ext.to_str()?))),
+        None => {
+            let name = file.file_name()?.to_str()?;
+            KNOWN_FILE_NAMES.contains(&name).then(|| escape(name))
+        }
+    }
+}
+
+/// The value each property has in `contents`, if it's detectable.
+fn detect(contents: &[u8]) -> Vec<(&'static str, String)> {
+    let mut detected = vec![];
+
+    let text = match std::str::from_utf8(contents) {
+        Ok(text) => text,
+        // We can't tell an 8-bit encoding apart from the others.
+        Err(_) => return detected,
+    };
+
+    let (charset, text) = match text.strip_prefix('\u{feff}') {
+        Some(text) => ("utf-8-bom", text),
+        None => ("utf-8", text),
+    };
+    detected.push(("charset", charset.to_owned()));
+
+    let crlf = text.matches("\r\n").count();
+    let lf = text.matches('\n').count() - crlf;
+    let cr = text.matches('\r').count() - crlf;
+    if let Some((_count, eol)) =
+        [(lf, "lf"), (crlf, "crlf"), (cr, "cr")].into_iter().max()
+        && 0 < lf + crlf + cr
+    {
+        detected.push(("end_of_line", eol.to_owned()));
+    }
+
+    let ends_with_newline = text.ends_with(['\n', '\r']);
+    detected.push(("insert_final_newline", ends_with_newline.to_string()));
+
+    let lines: Vec<&str> = text.lines().collect();
+
+    let has_trailing_whitespace =
+        lines.iter().any(|l| l.ends_with([' ', '\t']) && !l.trim().is_empty());
+    detected.push((
+        "trim_trailing_whitespace",
+        (!has_trailing_whitespace).to_string(),
+    ));
+
+    let tabs = lines.iter().filter(|l| l.starts_with('\t')).count();
+    let spaces = lines.iter().filter(|l| l.starts_with(' ')).count();
+    if tabs != spaces {
+        let style = if spaces < tabs { "tab" } else { "space" };
+        detected.push(("indent_style", style.to_owned()));
+
+        if style == "space"
+            && let Some(size) = indent_size(&lines)
+        {
+            detected.push(("indent_size", size.to_string()));
+        }
+    }
+
+    detected
+}
+
+/// The most common increase in the indentation between consecutive lines.
+fn indent_size(lines: &[&str]) -> Option<usize> {
+    let mut deltas: HashMap<usize, usize> = HashMap::new();
+    let mut prev = 0;
+
+    for line in lines.iter().filter(|l| !l.trim().is_empty()) {
+        if line.starts_with('\t') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        if prev < indent {
+            *deltas.entry(indent - prev).or_default() += 1;
+        }
+        prev = indent;
+    }
+
+    deltas
+        .into_iter()
+        .filter(|&(delta, _count)| matches!(delta, 2 | 3 | 4 | 8))
+        .max_by_key(|&(delta, count)| (count, delta))
+        .map(|(delta, _count)| delta)
+}
+
+/// The values which the majority of the voters agree on.
+fn elect(votes: Votes) -> Properties {
+    votes
+        .into_iter()
+        .filter_map(|(key, values)| {
+            let total: usize = values.values().sum();
+            let (value, count) = values.into_iter().max_by_key(|&(_, c)| c)?;
+            (total < count * 2).then(|| (key.to_owned(), value))
+        })
+        .collect()
+}
@@ -1,9 +1,9 @@
-//! An EditorConfig Core passing all the [editorconfig-core-test] tests.
+//! An EditorConfig Core, tested against the [editorconfig-core-test] suite.
 //!
 //! # Examples
 //!
 //! ```no_run
-//! use editorconfig_core::properties;
+//! use editorconfig_core::{Options, properties_with_options};
 //!
 //! // Let's define the property we want to extract.
 //!
@@ -27,10 +27,10 @@
 //! // Must be a full, normalized, valid unicode path.
 //! let path = "/home/myself/README.md";
 //!
-//! let mut properties = properties(path).unwrap();
+//! // Discard the properties which were unset.
+//! let options = Options::default().with_remove_unset(true);
 //!
-//! // Discard properties that was unset.
-//! properties.retain(|_key, value| !value.eq_ignore_ascii_case("unset"));
+//! let properties = properties_with_options(path, options).unwrap();
 //!
 //! // Extract the property.
 //! let eof = properties.get(EndOfLine::KEY).and_then(EndOfLine::from_str);
@@ -41,23 +41,92 @@
 //! - All the keys are already lowercased via `str::to_lowercase`.
 //! - The values are kept in their original form, except for the values of the ["Supported"](https://editorconfig.org/#supported-properties)
 //!   properties.
+//! - Before version 0.15.0 (see [`Options::version`]), inline comments are
+//!   stripped from the values, and `\#` and `\;` are unescaped. The literal
+//!   text of a value is available through [`Line::raw_value`]. Comments after
+//!   section headers are stripped too, and `:` separates keys from values like
+//!   `=` does.
 //!
 //! [editorconfig-core-test]: https://github.com/editorconfig/editorconfig-core-test
 
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "capi")]
+mod capi;
+mod diff;
+mod document;
+mod explain;
+mod export;
+mod format;
+#[cfg(feature = "git")]
+mod git;
 mod glob;
+mod init;
+mod lint;
+mod property;
+#[cfg(feature = "python")]
+mod python;
+mod snapshot;
+mod source;
+mod spec;
+mod tree;
 mod version;
+mod walk;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "watch")]
+mod watch;
 
+use std::borrow::Borrow;
 use std::collections::HashMap;
-use std::convert::identity;
-use std::fs::File;
-use std::io::{self, BufRead as _, BufReader};
+use std::fmt::Display;
+use std::io;
 use std::path::Path;
 
+#[cfg(feature = "async")]
+pub use crate::asynchronous::{
+    AsyncFileSystem, TokioFileSystem, properties_async,
+    properties_with_fs_async, properties_with_options_async,
+};
+pub use crate::diff::{FileDiff, PropertyChange, diff_properties, diff_tree};
+pub use crate::document::{Document, Line, LineKind, Section};
+pub use crate::explain::{
+    ConfigFile, Explanation, Origin, PairTrace, SectionTrace, explain,
+    explain_with_options,
+};
+pub use crate::export::{
+    clang_format_config, prettier_config, rustfmt_config, vscode_settings,
+};
+pub use crate::format::{FormatOptions, format};
+#[cfg(feature = "git")]
+pub use crate::git::GitTree;
 use crate::glob::Glob;
-pub use crate::version::Version;
+pub use crate::init::{infer, scaffold};
+pub use crate::lint::{
+    Diagnostic, Rule, Severity, lint, lint_file, lint_with_behavior,
+};
+pub use crate::property::{
+    KNOWN_PROPERTIES, KnownProperty, Value, ValueKind, typed_properties,
+};
+pub use crate::snapshot::Snapshot;
+use crate::source::load_document;
+pub use crate::source::{
+    ConfigSource, FileSystem, Mirror, properties_from_source,
+};
+pub use crate::spec::{
+    Behavior, MAX_KEY_LEN, MAX_SECTION_LEN, MAX_VALUE_LEN, SPEC_CHANGES,
+    SpecChange,
+};
+pub use crate::tree::{
+    TreeEntry, TreeOptions, files_with_property, resolve_tree,
+    resolve_tree_from_source,
+};
+pub use crate::version::{ParseVersionError, Version};
+#[cfg(feature = "watch")]
+pub use crate::watch::{PropertiesChange, WatchEvent, Watcher};
 
 /// Max. supported EditorConfig version.
-pub const MAX_VERSION: Version = Version { major: 0, minor: 17, patch: 2 };
+pub const MAX_VERSION: Version = Version::V0_17_2;
 
 #[derive(Debug)]
 pub enum Error {
@@ -66,17 +135,69 @@ pub enum Error {
     Io(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse => f.write_str("failed to parse an EditorConfig file"),
+            Self::InvalidPath => f.write_str("path is not valid unicode"),
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The options of a resolution, built from [`Options::default`] and the
+/// `with_*` methods.
+///
+/// The struct is `#[non_exhaustive]`, so that options can be added without
+/// breaking the callers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct Options<'a> {
     /// Another name for EditorConfig files (defaults to ".editorconfig").
     pub file_name: &'a str,
-    /// EditorConfig version to use (defaults to [`MAX_VERSION`]).
+    /// EditorConfig version to use (defaults to [`MAX_VERSION`]). The
+    /// behaviors which changed since are as they were at that version (see
+    /// [`SPEC_CHANGES`]).
     pub version: Version,
+    /// Remove the properties whose value is `unset` from the results, rather
+    /// than keeping them as `unset` (defaults to `false`, like the other
+    /// cores).
+    pub remove_unset: bool,
 }
 
 impl<'a> Default for Options<'a> {
     fn default() -> Self {
-        Self { file_name: ".editorconfig", version: MAX_VERSION }
+        Self {
+            file_name: ".editorconfig",
+            version: MAX_VERSION,
+            remove_unset: false,
+        }
+    }
+}
+
+impl<'a> Options<'a> {
+    /// Sets [`Options::file_name`].
+    pub fn with_file_name(self, file_name: &'a str) -> Self {
+        Self { file_name, ..self }
+    }
+
+    /// Sets [`Options::version`].
+    pub fn with_version(self, version: Version) -> Self {
+        Self { version, ..self }
+    }
+
+    /// Sets [`Options::remove_unset`].
+    pub fn with_remove_unset(self, remove_unset: bool) -> Self {
+        Self { remove_unset, ..self }
     }
 }
 
@@ -101,20 +222,62 @@ pub fn properties_with_options<P>(
 where
     P: AsRef<Path>,
 {
-    let normalized_path = normalize_path(path.as_ref())?;
-    let mut properties = HashMap::new();
+    resolve(path.as_ref(), &options, |dir| read_document(dir, &options))
+}
 
-    let ancestors: Vec<_> = path.as_ref().ancestors().skip(1).collect();
+/// Resolves the properties for the file at `path`, with `load` loading the
+/// EditorConfig file of a directory (if there's one).
+fn resolve<F, D>(
+    path: &Path,
+    options: &Options,
+    mut load: F,
+) -> Result<Properties, Error>
+where
+    F: FnMut(&Path) -> Result<Option<D>, Error>,
+    D: Borrow<Document>,
+{
+    let normalized_path = normalize_path(path)?;
+    let behavior = Behavior::of(options.version);
+    let mut properties = HashMap::new();
 
-    for dir in ancestors.iter().rev() {
-        parse_dir(dir, &normalized_path, &options, &mut properties)?;
+    for dir in ancestors(path) {
+        if let Some(document) = load(dir)? {
+            apply_document(
+                document.borrow(),
+                dir,
+                &normalized_path,
+                &behavior,
+                &mut properties,
+            )?;
+        }
     }
 
-    process_properties(&mut properties, &options);
+    finish_properties(&mut properties, options);
+
+    Ok(properties)
+}
+
+/// The directories which may contain an EditorConfig file affecting the file at
+/// `path`, from the root to the file's directory.
+fn ancestors(path: &Path) -> impl Iterator<Item = &Path> {
+    let ancestors: Vec<_> = path.ancestors().skip(1).collect();
+    ancestors.into_iter().rev()
+}
+
+fn finish_properties(properties: &mut Properties, options: &Options) {
+    process_properties(properties, options);
 
     properties.retain(|key, _value| key != "unset");
 
-    Ok(properties)
+    if options.remove_unset {
+        properties.retain(|_key, value| !is_unset(value));
+    }
+}
+
+/// Whether `value` is `unset` (case-insensitively, since only the values of
+/// "Supported" properties are lowercased).
+fn is_unset(value: &str) -> bool {
+    value.eq_ignore_ascii_case("unset")
 }
 
 /// Process and modify the properties to adhere to the specification at the
@@ -123,16 +286,16 @@ fn process_properties(
     properties: &mut HashMap<String, String>,
     options: &Options,
 ) {
-    // TODO: explain what's happening here.
-
-    const V0_9_0: Version = Version { major: 0, minor: 9, patch: 0 };
-
     const INDENT_STYLE: &str = "indent_style";
     const INDENT_SIZE: &str = "indent_size";
     const TAB_WIDTH: &str = "tab_width";
     const TAB: &str = "tab";
 
-    if options.version.cmp(&V0_9_0).is_ge() {
+    let behavior = Behavior::of(options.version);
+
+    if behavior.tab_indent_size {
+        // Indenting with tabs means an indentation level is a tab wide, which
+        // is `tab_width` wide (if it's set).
         if properties.get(INDENT_STYLE).is_some_and(|v| v == TAB)
             && !properties.contains_key(INDENT_SIZE)
         {
@@ -141,85 +304,115 @@ fn process_properties(
 
         if properties.get(INDENT_SIZE).is_some_and(|v| v == TAB)
             && let Some(tab_width) = properties.get(TAB_WIDTH)
+            && !is_unset(tab_width)
         {
             properties.insert(INDENT_SIZE.to_owned(), tab_width.to_owned());
         }
     }
 
+    // `tab_width` defaults to `indent_size` (unless it's `tab`, which would be
+    // circular, or `unset`, which leaves `tab_width` to the editor too).
     if let Some(indent_size) = properties.get(INDENT_SIZE)
         && !properties.contains_key(TAB_WIDTH)
-        && (options.version.cmp(&V0_9_0).is_lt() || indent_size != TAB)
+        && !is_unset(indent_size)
+        && (!behavior.tab_indent_size || indent_size != TAB)
     {
         properties.insert(TAB_WIDTH.to_owned(), indent_size.to_owned());
     }
 }
 
-fn parse_dir(
+/// Reads and parses the EditorConfig file at `ec_dir`, if there is one.
+fn read_document(
     ec_dir: &Path,
-    normalized_file_path: &str,
     options: &Options,
+) -> Result<Option<Document>, Error> {
+    load_document(&FileSystem, ec_dir, options)
+}
+
+fn apply_document(
+    document: &Document,
+    ec_dir: &Path,
+    normalized_file_path: &str,
+    behavior: &Behavior,
     properties: &mut HashMap<String, String>,
 ) -> Result<(), Error> {
-    const COMMENT: &[char] = &['#', ';'];
-
-    let ec_file_path = ec_dir.join(options.file_name);
-    let ec_file = match File::open(ec_file_path) {
-        Ok(f) => f,
-        Err(e) if e.kind() == io::ErrorKind::NotFound => {
-            // The EditorConfig file doesn't have to exist at any of the dirs.
-            return Ok(());
-        }
-        Err(e) => return Err(Error::Io(e)),
-    };
-
     let normalized_ec_dir = normalize_path(ec_dir)?;
 
-    let mut reader = BufReader::new(ec_file);
-
-    let mut line = String::new();
-
-    let mut section_matches_file = None;
+    apply_sections(document, behavior, properties, |_idx, section| {
+        section_matches(
+            section,
+            &normalized_ec_dir,
+            normalized_file_path,
+            behavior,
+        )
+    })
+}
 
-    while reader.read_line(&mut line).map_err(Error::Io)? != 0 {
-        let l = line
-            .strip_suffix('\n')
-            .unwrap_or(&line)
-            .strip_suffix('\r')
-            .unwrap_or(&line)
-            .trim();
+/// Applies the pairs of the sections (by their index in
+/// [`Document::sections`]) for which `is_match` holds.
+fn apply_sections<F>(
+    document: &Document,
+    behavior: &Behavior,
+    properties: &mut HashMap<String, String>,
+    mut is_match: F,
+) -> Result<(), Error>
+where
+    F: FnMut(usize, &Section) -> Result<bool, Error>,
+{
+    if document.is_root() {
+        // We walk from the root to the directory of the target file, so if
+        // an EditorConfig file is a root, it means that all the
+        // EditorConfig files "above" it should be discarded.
+        properties.clear();
+    }
 
-        if l.starts_with(COMMENT) {
-            // We ignore comment lines.
-        } else if let Some(is_match) =
-            parse_section(normalized_file_path, &normalized_ec_dir, l)?
-        {
-            section_matches_file = Some(is_match);
-        } else if section_matches_file.is_some_and(identity)
-            && let Some((key, value)) = parse_pair(l)
-        {
-            insert_pair(properties, key, value);
-        } else if section_matches_file.is_none()
-            && let Some((key, value)) = parse_pair(l)
-            && key.eq_ignore_ascii_case("root")
-            && value.eq_ignore_ascii_case("true")
-        {
-            // We walk from the root to the directory of the target file, so if
-            // an EditorConfig file is a root, it means that all the
-            // EditorConfig files "below" it should be discarded.
-            properties.clear();
+    for (idx, section) in document.sections().enumerate() {
+        if section.pattern().is_some_and(|p| !behavior.accepts_section(p)) {
+            continue;
         }
 
-        line.clear();
+        if is_match(idx, &section)? {
+            for (_line, key, value) in section.pairs() {
+                insert_pair(properties, key, value, behavior);
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Whether the file matches the glob of `section` (the preamble never matches).
+fn section_matches(
+    section: &Section,
+    normalized_ec_dir: &str,
+    normalized_file_path: &str,
+    behavior: &Behavior,
+) -> Result<bool, Error> {
+    let Some(pattern) = section.pattern() else { return Ok(false) };
+    let glob = Glob::with_behavior(normalized_ec_dir, pattern, behavior)
+        .map_err(|_| Error::Parse)?;
+    Ok(glob.is_match(normalized_file_path))
+}
+
 fn insert_pair(
     properties: &mut HashMap<String, String>,
     key: &str,
     value: &str,
+    behavior: &Behavior,
 ) {
+    if behavior.accepts_pair(key, &behavior.value(value)) {
+        let (key, value) = normalize_pair(key, value, behavior);
+        properties.insert(key, value);
+    }
+}
+
+/// Lowercases the key, and the value of the "Supported" properties (after
+/// stripping its inline comment, for the versions which have them).
+fn normalize_pair(
+    key: &str,
+    value: &str,
+    behavior: &Behavior,
+) -> (String, String) {
     const SPECIAL_KEYS: &[&str] = &[
         "end_of_line",
         "indent_style",
@@ -230,34 +423,14 @@ fn insert_pair(
     ];
 
     let key = key.to_lowercase();
+    let value = behavior.value(value);
     let value = if SPECIAL_KEYS.contains(&key.as_str()) {
         value.to_lowercase()
     } else {
-        value.to_owned()
+        value.into_owned()
     };
 
-    properties.insert(key, value);
-}
-
-fn parse_section(
-    normalized_file_path: &str,
-    normalized_ec_dir: &str,
-    line: &str,
-) -> Result<Option<bool>, Error> {
-    let Some(pattern) =
-        line.strip_prefix('[').and_then(|l| l.strip_suffix(']'))
-    else {
-        return Ok(None);
-    };
-    let glob =
-        Glob::new(normalized_ec_dir, pattern).map_err(|_| Error::Parse)?;
-    Ok(Some(glob.is_match(normalized_file_path)))
-}
-
-fn parse_pair(line: &str) -> Option<(&str, &str)> {
-    let (key, value) = line.split_once('=')?;
-    let (key, value) = (key.trim(), value.trim());
-    (!key.is_empty()).then_some((key, value))
+    (key, value)
 }
 
 fn normalize_path(path: &Path) -> Result<String, Error> {
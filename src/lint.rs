@@ -0,0 +1,437 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::glob::Glob;
+use crate::property::{KNOWN_PROPERTIES, KnownProperty, suggest};
+use crate::walk::{Skip, walk};
+use crate::{
+    Behavior, Document, Error, Line, LineKind, MAX_KEY_LEN, MAX_SECTION_LEN,
+    MAX_VALUE_LEN, Options, normalize_path,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The 1-based line.
+    pub line: usize,
+    /// The 1-based column (in characters).
+    pub column: usize,
+    pub rule: Rule,
+    pub message: String,
+}
+
+/// The lint rules. Their codes (see [`Rule::code`]) are stable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rule {
+    UnknownProperty,
+    InvalidValue,
+    DuplicateKey,
+    UnmatchedSection,
+    ShadowedSection,
+    MisplacedRoot,
+    NonLowercaseKey,
+    InvalidGlob,
+    MalformedLine,
+    TooLong,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Rule {
+    pub const ALL: &[Self] = &[
+        Self::UnknownProperty,
+        Self::InvalidValue,
+        Self::DuplicateKey,
+        Self::UnmatchedSection,
+        Self::ShadowedSection,
+        Self::MisplacedRoot,
+        Self::NonLowercaseKey,
+        Self::InvalidGlob,
+        Self::MalformedLine,
+        Self::TooLong,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownProperty => "EC001",
+            Self::InvalidValue => "EC002",
+            Self::DuplicateKey => "EC003",
+            Self::UnmatchedSection => "EC004",
+            Self::ShadowedSection => "EC005",
+            Self::MisplacedRoot => "EC006",
+            Self::NonLowercaseKey => "EC007",
+            Self::InvalidGlob => "EC008",
+            Self::MalformedLine => "EC009",
+            Self::TooLong => "EC010",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UnknownProperty => "unknown-property",
+            Self::InvalidValue => "invalid-value",
+            Self::DuplicateKey => "duplicate-key",
+            Self::UnmatchedSection => "unmatched-section",
+            Self::ShadowedSection => "shadowed-section",
+            Self::MisplacedRoot => "misplaced-root",
+            Self::NonLowercaseKey => "non-lowercase-key",
+            Self::InvalidGlob => "invalid-glob",
+            Self::MalformedLine => "malformed-line",
+            Self::TooLong => "too-long",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::InvalidValue
+            | Self::InvalidGlob
+            | Self::MalformedLine
+            | Self::TooLong => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: {} {} ({})",
+            self.line,
+            self.column,
+            self.rule.code(),
+            self.message,
+            self.rule.name()
+        )
+    }
+}
+
+/// Lints an EditorConfig document on its own, i.e., without the checks which
+/// need the files around it (see [`lint_file`]).
+pub fn lint(document: &Document) -> Vec<Diagnostic> {
+    lint_with_behavior(document, &Behavior::default())
+}
+
+/// Like [`lint`], but the document is checked as it's read with `behavior`
+/// (e.g., the values are checked after their inline comments are stripped, if
+/// they are at all).
+pub fn lint_with_behavior(
+    document: &Document,
+    behavior: &Behavior,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    for line in document.lines.iter() {
+        if line.kind != LineKind::Invalid {
+            continue;
+        }
+
+        let trimmed = line.text.trim();
+        let message = if trimmed.starts_with('[') {
+            "section header is missing its closing `]`"
+        } else {
+            "expected a comment, a `[section]` or a `key = value` pair"
+        };
+        let offset = line.text.len() - line.text.trim_start().len();
+        diagnostics.push(diagnostic(
+            line,
+            offset,
+            Rule::MalformedLine,
+            message.to_owned(),
+        ));
+    }
+
+    for (idx, section) in document.sections().enumerate() {
+        let is_preamble = idx == 0;
+
+        if let Some(header) = section.header
+            && let Some(pattern) = section.pattern()
+            && !behavior.accepts_section(pattern)
+        {
+            diagnostics.push(diagnostic(
+                header,
+                pattern_start(header),
+                Rule::TooLong,
+                format!(
+                    "the glob is longer than {MAX_SECTION_LEN} bytes, so the \
+                     section is ignored"
+                ),
+            ));
+        } else if let Some(header) = section.header
+            && let Some(pattern) = section.pattern()
+            && Glob::with_behavior("", pattern, behavior).is_err()
+        {
+            diagnostics.push(diagnostic(
+                header,
+                pattern_start(header),
+                Rule::InvalidGlob,
+                format!(
+                    "`[{pattern}]` is not a valid glob, so it never matches"
+                ),
+            ));
+        }
+
+        let mut seen: HashMap<String, usize> = HashMap::new();
+
+        for (line, key, value) in section.pairs() {
+            let (key_start, value_start) = match &line.kind {
+                LineKind::Pair { key, value } => (key.start, value.start),
+                _ => unreachable!("`pairs` only yields pairs"),
+            };
+            let lowercase_key = key.to_lowercase();
+
+            if !behavior.accepts_pair(key, &behavior.value(value)) {
+                let (start, what, max) = if MAX_KEY_LEN < key.len() {
+                    (key_start, "key", MAX_KEY_LEN)
+                } else {
+                    (value_start, "value", MAX_VALUE_LEN)
+                };
+                diagnostics.push(diagnostic(
+                    line,
+                    start,
+                    Rule::TooLong,
+                    format!(
+                        "the {what} is longer than {max} bytes, so the pair \
+                         is ignored"
+                    ),
+                ));
+                continue;
+            }
+
+            if key != lowercase_key {
+                diagnostics.push(diagnostic(
+                    line,
+                    key_start,
+                    Rule::NonLowercaseKey,
+                    format!(
+                        "key `{key}` should be written as `{lowercase_key}`"
+                    ),
+                ));
+            }
+
+            if let Some(first) = seen.insert(lowercase_key.clone(), line.number)
+            {
+                diagnostics.push(diagnostic(
+                    line,
+                    key_start,
+                    Rule::DuplicateKey,
+                    format!(
+                        "`{lowercase_key}` is already set at line {first} of \
+                         this section"
+                    ),
+                ));
+            }
+
+            if lowercase_key == "root" && !is_preamble {
+                diagnostics.push(diagnostic(
+                    line,
+                    key_start,
+                    Rule::MisplacedRoot,
+                    "`root` only has an effect before the first section"
+                        .to_owned(),
+                ));
+                continue;
+            }
+
+            let value = behavior.value(value);
+            match KnownProperty::get(&lowercase_key) {
+                Some(property) if !property.is_valid(&value) => {
+                    let mut message = format!(
+                        "`{value}` is not a valid value for `{}`",
+                        property.key
+                    );
+                    if let Some(s) = suggest(&value, property.keywords()) {
+                        message.push_str(&format!(", did you mean `{s}`?"));
+                    }
+                    diagnostics.push(diagnostic(
+                        line,
+                        value_start,
+                        Rule::InvalidValue,
+                        message,
+                    ));
+                }
+                Some(_property) => {}
+                None => {
+                    let mut message = format!("unknown property `{key}`");
+                    let keys = KNOWN_PROPERTIES.iter().map(|p| p.key);
+                    if let Some(s) = suggest(&lowercase_key, keys) {
+                        message.push_str(&format!(", did you mean `{s}`?"));
+                    }
+                    diagnostics.push(diagnostic(
+                        line,
+                        key_start,
+                        Rule::UnknownProperty,
+                        message,
+                    ));
+                }
+            }
+        }
+    }
+
+    diagnostics.extend(shadowed_sections(document, |a, b| {
+        // Without the files, we only know that identical globs match the same
+        // files.
+        a.pattern() == b.pattern()
+    }));
+
+    sort(&mut diagnostics);
+    diagnostics
+}
+
+/// Lints the EditorConfig file at `path`, including the checks against the
+/// files in its directory tree (i.e., sections which never match any file, or
+/// which are shadowed by later ones).
+///
+/// Subdirectories which have a root EditorConfig file of their own are not
+/// considered part of the tree, nor are `.git` directories and the files
+/// ignored by ignore files (e.g., `.gitignore`).
+pub fn lint_file<P>(path: P, options: Options) -> Result<Vec<Diagnostic>, Error>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path).map_err(Error::Io)?;
+    let behavior = Behavior::of(options.version);
+    let document = Document::parse_with_behavior(&contents, &behavior);
+    let mut diagnostics = lint_with_behavior(&document, &behavior);
+    diagnostics.retain(|d| d.rule != Rule::ShadowedSection);
+
+    let ec_dir = path.parent().ok_or(Error::InvalidPath)?;
+    let normalized_ec_dir = normalize_path(ec_dir)?;
+
+    let file_name = options.file_name.to_owned();
+    let skip = Skip { ignored: true, hidden: false };
+    let (_dirs, files) =
+        walk(ec_dir, skip, move |dir| is_root_dir(dir, &file_name))
+            .map_err(Error::Io)?;
+    let files = files
+        .into_iter()
+        .map(|f| normalize_path(&f))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // The files each section header (by line number) matches.
+    let mut matches: HashMap<usize, HashSet<usize>> = HashMap::new();
+
+    for section in document.sections() {
+        let (Some(header), Some(pattern)) = (section.header, section.pattern())
+        else {
+            continue;
+        };
+        let Ok(glob) =
+            Glob::with_behavior(&normalized_ec_dir, pattern, &behavior)
+        else {
+            // Already reported as an invalid glob.
+            continue;
+        };
+
+        let matched: HashSet<usize> = files
+            .iter()
+            .enumerate()
+            .filter(|(_idx, f)| glob.is_match(f))
+            .map(|(idx, _f)| idx)
+            .collect();
+
+        if matched.is_empty() {
+            diagnostics.push(diagnostic(
+                header,
+                pattern_start(header),
+                Rule::UnmatchedSection,
+                format!("`[{pattern}]` doesn't match any file"),
+            ));
+        }
+
+        matches.insert(header.number, matched);
+    }
+
+    diagnostics.extend(shadowed_sections(&document, |a, b| {
+        let (Some(a), Some(b)) =
+            (matches.get(&a.number), matches.get(&b.number))
+        else {
+            return false;
+        };
+        !a.is_empty() && a.is_subset(b)
+    }));
+
+    sort(&mut diagnostics);
+    Ok(diagnostics)
+}
+
+/// Finds the sections of which all the keys are set again by a later section
+/// that matches (at least) all the same files, per `is_covered_by`.
+fn shadowed_sections<F>(
+    document: &Document,
+    is_covered_by: F,
+) -> Vec<Diagnostic>
+where
+    F: Fn(&Line, &Line) -> bool,
+{
+    let sections: Vec<(&Line, HashSet<String>)> = document
+        .sections()
+        .filter_map(|section| {
+            let header = section.header?;
+            section.pattern()?;
+            let keys = section.pairs().map(|(_l, k, _v)| k.to_lowercase());
+            Some((header, keys.collect()))
+        })
+        .collect();
+
+    let mut diagnostics = vec![];
+
+    for (idx, (header, keys)) in sections.iter().enumerate() {
+        if keys.is_empty() {
+            continue;
+        }
+
+        let shadowing =
+            sections[idx + 1..].iter().find(|(later, later_keys)| {
+                keys.is_subset(later_keys) && is_covered_by(header, later)
+            });
+
+        if let Some((later, _keys)) = shadowing {
+            diagnostics.push(diagnostic(
+                header,
+                pattern_start(header),
+                Rule::ShadowedSection,
+                format!(
+                    "all the properties of this section are overridden by the \
+                     section at line {}",
+                    later.number
+                ),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+fn is_root_dir(dir: &Path, file_name: &str) -> bool {
+    let ec_file: PathBuf = dir.join(file_name);
+    fs::read_to_string(ec_file)
+        .is_ok_and(|contents| Document::parse(&contents).is_root())
+}
+
+fn pattern_start(header: &Line) -> usize {
+    match &header.kind {
+        LineKind::Section { pattern } => pattern.start - 1,
+        _ => 0,
+    }
+}
+
+fn diagnostic(
+    line: &Line,
+    offset: usize,
+    rule: Rule,
+    message: String,
+) -> Diagnostic {
+    let column = line.text[..offset].chars().count() + 1;
+    Diagnostic { line: line.number, column, rule, message }
+}
+
+fn sort(diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by_key(|d| (d.line, d.column, d.rule));
+}
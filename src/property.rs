@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::Properties;
+
+/// A property defined by the EditorConfig specification (or the widely
+/// supported `max_line_length`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownProperty {
+    pub key: &'static str,
+    pub description: &'static str,
+    /// The forms a value may take (besides `unset`).
+    pub values: &'static [ValueKind],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// A fixed, case-insensitive word.
+    Keyword(&'static str),
+    /// A whole number greater than zero.
+    PositiveInteger,
+    /// A natural language code, e.g. `en` or `en-US`.
+    Language,
+}
+
+/// A property value, typed by the forms its property accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Value {
+    /// `unset`, i.e., the property should be left to the editor.
+    Unset,
+    /// One of the property's keywords (lowercased), e.g. `tab`.
+    Keyword(String),
+    Integer(u32),
+    /// A natural language code, e.g. `en` or `en-US`.
+    Language(String),
+    /// The value of an unknown property, or an invalid value of a known one.
+    Other(String),
+}
+
+pub const KNOWN_PROPERTIES: &[KnownProperty] = &[
+    KnownProperty {
+        key: "root",
+        description: "Special property that should be specified at the top of \
+                      the file outside of any sections. Set to `true` to stop \
+                      the search for EditorConfig files on the current file.",
+        values: &[ValueKind::Keyword("true"), ValueKind::Keyword("false")],
+    },
+    KnownProperty {
+        key: "indent_style",
+        description: "Set to `tab` or `space` to use hard tabs or soft tabs \
+                      respectively.",
+        values: &[ValueKind::Keyword("tab"), ValueKind::Keyword("space")],
+    },
+    KnownProperty {
+        key: "indent_size",
+        description: "A whole number defining the number of columns used for \
+                      each indentation level and the width of soft tabs (when \
+                      supported). When set to `tab`, the value of `tab_width` \
+                      (if specified) will be used.",
+        values: &[ValueKind::PositiveInteger, ValueKind::Keyword("tab")],
+    },
+    KnownProperty {
+        key: "tab_width",
+        description: "A whole number defining the number of columns used to \
+                      represent a tab character. This defaults to the value of \
+                      `indent_size` and doesn't usually need to be specified.",
+        values: &[ValueKind::PositiveInteger],
+    },
+    KnownProperty {
+        key: "end_of_line",
+        description: "Set to `lf`, `cr`, or `crlf` to control how line breaks \
+                      are represented.",
+        values: &[
+            ValueKind::Keyword("lf"),
+            ValueKind::Keyword("cr"),
+            ValueKind::Keyword("crlf"),
+        ],
+    },
+    KnownProperty {
+        key: "charset",
+        description: "Set to `latin1`, `utf-8`, `utf-8-bom`, `utf-16be` or \
+                      `utf-16le` to control the character set.",
+        values: &[
+            ValueKind::Keyword("latin1"),
+            ValueKind::Keyword("utf-8"),
+            ValueKind::Keyword("utf-8-bom"),
+            ValueKind::Keyword("utf-16be"),
+            ValueKind::Keyword("utf-16le"),
+        ],
+    },
+    KnownProperty {
+        key: "spelling_language",
+        description: "Sets the natural language that should be used for spell \
+                      checking, in the `ss` or `ss-TT` form (e.g., `en-US`).",
+        values: &[ValueKind::Language],
+    },
+    KnownProperty {
+        key: "trim_trailing_whitespace",
+        description: "Set to `true` to remove all whitespace characters \
+                      preceding newline characters in the file and `false` to \
+                      ensure it doesn't.",
+        values: &[ValueKind::Keyword("true"), ValueKind::Keyword("false")],
+    },
+    KnownProperty {
+        key: "insert_final_newline",
+        description: "Set to `true` to ensure file ends with a newline when \
+                      saving and `false` to ensure it doesn't.",
+        values: &[ValueKind::Keyword("true"), ValueKind::Keyword("false")],
+    },
+    KnownProperty {
+        key: "max_line_length",
+        description: "Forces hard line wrapping after the amount of characters \
+                      specified. Set to `off` to turn this feature off (and use \
+                      the editor settings).",
+        values: &[ValueKind::PositiveInteger, ValueKind::Keyword("off")],
+    },
+];
+
+impl KnownProperty {
+    /// Looks up a known property (case-insensitively).
+    pub fn get(key: &str) -> Option<&'static Self> {
+        KNOWN_PROPERTIES.iter().find(|p| p.key.eq_ignore_ascii_case(key))
+    }
+
+    /// Whether `value` is valid for this property (`unset` is always valid).
+    pub fn is_valid(&self, value: &str) -> bool {
+        value.eq_ignore_ascii_case("unset")
+            || self.values.iter().any(|kind| kind.is_valid(value))
+    }
+
+    /// The keywords this property accepts.
+    pub fn keywords(&self) -> impl Iterator<Item = &'static str> {
+        self.values.iter().filter_map(|kind| match kind {
+            ValueKind::Keyword(keyword) => Some(*keyword),
+            _ => None,
+        })
+    }
+}
+
+impl ValueKind {
+    pub fn is_valid(&self, value: &str) -> bool {
+        match self {
+            Self::Keyword(keyword) => keyword.eq_ignore_ascii_case(value),
+            // Only digits, as `u32::from_str` also accepts a leading `+`.
+            Self::PositiveInteger => {
+                value.bytes().all(|b| b.is_ascii_digit())
+                    && value.parse::<u32>().is_ok_and(|n| 0 < n)
+            }
+            Self::Language => {
+                let (lang, region) =
+                    value.split_once('-').unwrap_or((value, ""));
+                let is_alpha = |s: &str, n| {
+                    s.len() == n && s.bytes().all(|b| b.is_ascii_alphabetic())
+                };
+                is_alpha(lang, 2) && (region.is_empty() || is_alpha(region, 2))
+            }
+        }
+    }
+}
+
+impl Value {
+    pub fn is_unset(&self) -> bool {
+        matches!(self, Self::Unset)
+    }
+
+    /// Types the `value` of the property `key`.
+    pub fn parse(key: &str, value: &str) -> Self {
+        if value.eq_ignore_ascii_case("unset") {
+            return Self::Unset;
+        }
+
+        let Some(property) = KnownProperty::get(key) else {
+            return Self::Other(value.to_owned());
+        };
+
+        let kind = property.values.iter().find(|kind| kind.is_valid(value));
+        match kind {
+            Some(ValueKind::Keyword(keyword)) => {
+                Self::Keyword(keyword.to_string())
+            }
+            Some(ValueKind::PositiveInteger) => match value.parse() {
+                Ok(n) => Self::Integer(n),
+                Err(_e) => Self::Other(value.to_owned()),
+            },
+            Some(ValueKind::Language) => Self::Language(value.to_owned()),
+            None => Self::Other(value.to_owned()),
+        }
+    }
+}
+
+/// Types the values of resolved `properties` (see [`Value::parse`]), so the
+/// properties which were unset are [`Value::Unset`].
+pub fn typed_properties(properties: &Properties) -> HashMap<String, Value> {
+    properties
+        .iter()
+        .map(|(key, value)| (key.clone(), Value::parse(key, value)))
+        .collect()
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unset => f.write_str("unset"),
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Keyword(s) | Self::Language(s) | Self::Other(s) => {
+                f.write_str(s)
+            }
+        }
+    }
+}
+
+/// The candidate closest to `s` (by edit distance), if it's close enough to be
+/// a likely typo.
+pub(crate) fn suggest<'a, I>(s: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let s = s.to_lowercase();
+    let max_distance = (s.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|c| (edit_distance(&s, c), c))
+        .filter(|&(d, _c)| d <= max_distance)
+        .min_by_key(|&(d, _c)| d)
+        .map(|(_d, c)| c)
+}
+
+/// The edit distance between `a` and `b`, where a transposition of adjacent
+/// characters counts as a single edit (i.e., the "optimal string alignment"
+/// distance).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // `d[i][j]` is the distance between `a[..i]` and `b[..j]`.
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    (0..=a.len()).for_each(|i| d[i][0] = i);
+    (0..=b.len()).for_each(|j| d[0][j] = j);
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+
+            if 1 < i && 1 < j && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
@@ -0,0 +1,100 @@
+//! Python bindings modelled on the API of the [Python core] (but not a drop-in
+//! replacement for it, see the README).
+//!
+//! [Python core]: https://github.com/editorconfig/editorconfig-core-py
+
+use std::path::Path;
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::{Error, MAX_VERSION, Options, Version, properties_with_options};
+
+create_exception!(
+    editorconfig_core,
+    EditorConfigError,
+    PyException,
+    "The parent of all the EditorConfig errors."
+);
+create_exception!(
+    editorconfig_core,
+    ParsingError,
+    EditorConfigError,
+    "An EditorConfig file couldn't be parsed."
+);
+create_exception!(
+    editorconfig_core,
+    PathError,
+    EditorConfigError,
+    "The path of the file isn't a full, valid unicode path."
+);
+create_exception!(
+    editorconfig_core,
+    VersionError,
+    EditorConfigError,
+    "The version is invalid, or greater than the current version."
+);
+
+/// Returns the properties of the file at `filename` (a full path), as a dict.
+///
+/// `conf_filename` is another name for EditorConfig files, and `version` is
+/// the EditorConfig version to use (e.g., `"0.12.0"`).
+#[pyfunction]
+#[pyo3(signature = (filename, conf_filename = None, version = None))]
+fn get_properties<'py>(
+    py: Python<'py>,
+    filename: &str,
+    conf_filename: Option<&str>,
+    version: Option<&str>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let path = Path::new(filename);
+    if !path.is_absolute() {
+        return Err(PathError::new_err("Input file must be a full path name."));
+    }
+
+    let mut options = Options::default();
+    if let Some(file_name) = conf_filename {
+        options.file_name = file_name;
+    }
+    if let Some(version) = version {
+        options.version = version
+            .parse::<Version>()
+            .map_err(|e| VersionError::new_err(format!("{version}: {e}")))?;
+        if !options.version.is_supported() {
+            return Err(VersionError::new_err(
+                "Required version is greater than the current version.",
+            ));
+        }
+    }
+
+    let properties = py
+        .detach(|| properties_with_options(path, options))
+        .map_err(|e| match e {
+            Error::Parse => ParsingError::new_err(e.to_string()),
+            Error::InvalidPath => PathError::new_err(e.to_string()),
+            Error::Io(e) => e.into(),
+        })?;
+
+    let mut properties: Vec<_> = properties.into_iter().collect();
+    properties.sort_unstable();
+
+    let dict = PyDict::new(py);
+    for (key, value) in properties {
+        dict.set_item(key, value)?;
+    }
+    Ok(dict)
+}
+
+#[pymodule]
+fn editorconfig_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add_function(wrap_pyfunction!(get_properties, m)?)?;
+    m.add("EditorConfigError", py.get_type::<EditorConfigError>())?;
+    m.add("ParsingError", py.get_type::<ParsingError>())?;
+    m.add("PathError", py.get_type::<PathError>())?;
+    m.add("VersionError", py.get_type::<VersionError>())?;
+    m.add("__version__", MAX_VERSION.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,433 @@
+use std::collections::HashMap;
+use std::fs::{self, Metadata};
+use std::io;
+use std::path::{self, Path};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+use crate::glob::Glob;
+use crate::walk::{Skip, walk};
+use crate::{
+    Behavior, Document, Error, Line, LineKind, Options, Properties, Version,
+    ancestors, apply_document, apply_sections, finish_properties,
+    normalize_path, read_document,
+};
+
+/// Identifies snapshots, followed by the version of their format.
+const MAGIC: &[u8; 8] = b"ECSNAP\0\0";
+const FORMAT_VERSION: u32 = 1;
+
+/// The EditorConfig files under a directory (and above it), compiled ahead of
+/// time, so properties can be resolved without reading and parsing them.
+///
+/// A snapshot holds the sections and pairs of every EditorConfig file, with
+/// the globs of the sections translated into regexes, and the modification
+/// times and sizes of the files. It can be written to a binary buffer, and read
+/// back (the globs are compiled as the files are first matched against them).
+///
+/// When resolving, an EditorConfig file which changed since the snapshot was
+/// compiled (or which it doesn't have) is read and parsed as usual, so a stale
+/// snapshot is slower, but never wrong (as long as the modification times are
+/// reliable).
+#[derive(Debug)]
+pub struct Snapshot {
+    file_name: String,
+    version: Version,
+    /// By directory (which is valid unicode, like the paths being resolved).
+    entries: HashMap<String, Entry>,
+}
+
+/// A compiled EditorConfig file.
+#[derive(Debug)]
+struct Entry {
+    /// Since the Unix epoch.
+    modified: Duration,
+    len: u64,
+    /// Only the section headers and the pairs.
+    document: Document,
+    /// The regexes of the globs by the index of their section (`None` for the
+    /// preamble, and `Some(None)` for an invalid glob).
+    regexes: Vec<Option<Option<String>>>,
+    /// The regexes are only compiled when a file is first matched against
+    /// them, since they can't be stored compiled.
+    globs: Vec<OnceLock<Option<Glob>>>,
+}
+
+impl Snapshot {
+    /// Compiles the EditorConfig files under `root` (skipping `.git`
+    /// directories), and the ones in the directories above it.
+    pub fn compile<P>(root: P, options: Options) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let root = path::absolute(root.as_ref()).map_err(Error::Io)?;
+        let (mut dirs, _files) =
+            walk(&root, Skip::default(), |_dir| false).map_err(Error::Io)?;
+        dirs.extend(root.ancestors().skip(1).map(Path::to_path_buf));
+
+        let behavior = Behavior::of(options.version);
+        let mut entries = HashMap::new();
+
+        for dir in dirs {
+            // The metadata is taken first, so if the file changes while it's
+            // read, the entry is already stale.
+            let Some(metadata) = metadata(&dir, options.file_name)? else {
+                continue;
+            };
+            let Some(document) = read_document(&dir, &options)? else {
+                continue;
+            };
+            let entry = Entry::compile(document, &dir, &metadata, &behavior)?;
+            let dir = dir.into_os_string().into_string();
+            entries.insert(dir.map_err(|_dir| Error::InvalidPath)?, entry);
+        }
+
+        Ok(Self {
+            file_name: options.file_name.to_owned(),
+            version: options.version,
+            entries,
+        })
+    }
+
+    /// Like [`properties_with_options`](crate::properties_with_options), but
+    /// takes the EditorConfig files which didn't change from the snapshot.
+    ///
+    /// If `options` don't match the ones the snapshot was compiled with, it's
+    /// not used at all.
+    pub fn properties<P>(
+        &self,
+        path: P,
+        options: Options,
+    ) -> Result<Properties, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if options.file_name != self.file_name
+            || options.version != self.version
+        {
+            return crate::properties_with_options(path, options);
+        }
+
+        let normalized_path = normalize_path(path)?;
+        let behavior = Behavior::of(options.version);
+        let mut properties = HashMap::new();
+
+        for dir in ancestors(path) {
+            let Some(metadata) = metadata(dir, options.file_name)? else {
+                continue;
+            };
+
+            let fresh = dir
+                .to_str()
+                .and_then(|dir| self.entries.get(dir))
+                .filter(|e| e.is_fresh(&metadata));
+            if let Some(entry) = fresh {
+                apply_sections(
+                    &entry.document,
+                    &behavior,
+                    &mut properties,
+                    |idx, _section| entry.is_match(idx, &normalized_path),
+                )?;
+            } else if let Some(document) = read_document(dir, &options)? {
+                apply_document(
+                    &document,
+                    dir,
+                    &normalized_path,
+                    &behavior,
+                    &mut properties,
+                )?;
+            }
+        }
+
+        finish_properties(&mut properties, &options);
+
+        Ok(properties)
+    }
+
+    /// Whether any of the EditorConfig files in the snapshot changed (or was
+    /// removed) since it was compiled. New EditorConfig files aren't noticed.
+    pub fn is_stale(&self) -> bool {
+        self.entries.iter().any(|(dir, entry)| {
+            !matches!(
+                metadata(Path::new(dir), &self.file_name),
+                Ok(Some(metadata)) if entry.is_fresh(&metadata)
+            )
+        })
+    }
+
+    /// The snapshot as a binary buffer (see [`Snapshot::from_bytes`]).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer(MAGIC.to_vec());
+        w.u32(FORMAT_VERSION);
+        w.str(&self.file_name);
+        w.u32(self.version.major);
+        w.u32(self.version.minor);
+        w.u32(self.version.patch);
+
+        w.len(self.entries.len());
+        for (dir, entry) in self.entries.iter() {
+            w.str(dir);
+            w.u64(entry.modified.as_secs());
+            w.u32(entry.modified.subsec_nanos());
+            w.u64(entry.len);
+
+            w.len(entry.document.lines.len());
+            for line in entry.document.lines.iter() {
+                w.len(line.number);
+                w.str(&line.text);
+                match &line.kind {
+                    LineKind::Section { pattern } => {
+                        w.0.push(0);
+                        w.len(pattern.start);
+                        w.len(pattern.end);
+                    }
+                    LineKind::Pair { key, value } => {
+                        w.0.push(1);
+                        w.len(key.start);
+                        w.len(key.end);
+                        w.len(value.start);
+                        w.len(value.end);
+                    }
+                    _ => unreachable!("only sections and pairs are kept"),
+                }
+            }
+
+            w.len(entry.regexes.len());
+            for regex in entry.regexes.iter() {
+                match regex {
+                    None => w.0.push(0),
+                    Some(Some(regex)) => {
+                        w.0.push(1);
+                        w.str(regex);
+                    }
+                    Some(None) => w.0.push(2),
+                }
+            }
+        }
+
+        w.0
+    }
+
+    /// Reads a snapshot from a buffer of [`Snapshot::to_bytes`].
+    ///
+    /// A buffer which isn't a snapshot (or of an older format) is an
+    /// [`io::ErrorKind::InvalidData`] error.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::decode(&mut Reader(bytes)).map_err(Error::Io)
+    }
+
+    /// Writes the snapshot to the file at `path`.
+    pub fn write<P>(&self, path: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        fs::write(path, self.to_bytes()).map_err(Error::Io)
+    }
+
+    /// Reads a snapshot from the file at `path`.
+    pub fn read<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_bytes(&fs::read(path).map_err(Error::Io)?)
+    }
+
+    fn decode(r: &mut Reader) -> io::Result<Self> {
+        if r.take(MAGIC.len())? != MAGIC || r.u32()? != FORMAT_VERSION {
+            return Err(invalid_data("not a snapshot of this format"));
+        }
+
+        let file_name = r.str()?.to_owned();
+        let version = Version::new(r.u32()?, r.u32()?, r.u32()?);
+
+        let mut entries = HashMap::new();
+        for _ in 0..r.u32()? {
+            let dir = r.str()?.to_owned();
+            let (secs, nanos) = (r.u64()?, r.u32()?);
+            if 1_000_000_000 <= nanos {
+                return Err(invalid_data("invalid modification time"));
+            }
+            let modified = Duration::new(secs, nanos);
+            let len = r.u64()?;
+
+            let mut lines = vec![];
+            for _ in 0..r.u32()? {
+                let number = r.len()?;
+                let text = r.str()?.to_owned();
+                let kind = match r.take(1)?[0] {
+                    0 => LineKind::Section { pattern: r.len()?..r.len()? },
+                    1 => LineKind::Pair {
+                        key: r.len()?..r.len()?,
+                        value: r.len()?..r.len()?,
+                    },
+                    _ => return Err(invalid_data("invalid line kind")),
+                };
+                if !kind_in_bounds(&kind, &text) {
+                    return Err(invalid_data("line range out of bounds"));
+                }
+                lines.push(Line { number, text, kind });
+            }
+
+            let mut regexes = vec![];
+            for _ in 0..r.u32()? {
+                regexes.push(match r.take(1)?[0] {
+                    0 => None,
+                    1 => Some(Some(r.str()?.to_owned())),
+                    2 => Some(None),
+                    _ => return Err(invalid_data("invalid glob")),
+                });
+            }
+
+            let globs = regexes.iter().map(|_| OnceLock::new()).collect();
+            let document = Document { lines };
+            let entry = Entry { modified, len, document, regexes, globs };
+            entries.insert(dir, entry);
+        }
+
+        if !r.0.is_empty() {
+            return Err(invalid_data("trailing bytes"));
+        }
+
+        Ok(Self { file_name, version, entries })
+    }
+}
+
+impl Entry {
+    fn compile(
+        mut document: Document,
+        dir: &Path,
+        metadata: &Metadata,
+        behavior: &Behavior,
+    ) -> Result<Self, Error> {
+        let normalized_dir = normalize_path(dir)?;
+        let regexes: Vec<_> = document
+            .sections()
+            .map(|section| {
+                section.pattern().map(|pattern| {
+                    Glob::regex(&normalized_dir, pattern, behavior).ok()
+                })
+            })
+            .collect();
+
+        document.lines.retain(|line| {
+            matches!(
+                line.kind,
+                LineKind::Section { .. } | LineKind::Pair { .. }
+            )
+        });
+
+        Ok(Self {
+            modified: modified(metadata).map_err(Error::Io)?,
+            len: metadata.len(),
+            document,
+            globs: regexes.iter().map(|_| OnceLock::new()).collect(),
+            regexes,
+        })
+    }
+
+    fn is_fresh(&self, metadata: &Metadata) -> bool {
+        metadata.len() == self.len
+            && modified(metadata).is_ok_and(|m| m == self.modified)
+    }
+
+    /// Whether the glob of the section at `idx` matches the file.
+    fn is_match(
+        &self,
+        idx: usize,
+        normalized_path: &str,
+    ) -> Result<bool, Error> {
+        let Some(Some(regex)) = self.regexes.get(idx) else { return Ok(false) };
+        let glob = regex.as_ref().and_then(|regex| {
+            self.globs[idx]
+                .get_or_init(|| Glob::from_regex(regex).ok())
+                .as_ref()
+        });
+        glob.map(|glob| glob.is_match(normalized_path)).ok_or(Error::Parse)
+    }
+}
+
+/// The metadata of the EditorConfig file in `dir`, if there's one.
+fn metadata(dir: &Path, file_name: &str) -> Result<Option<Metadata>, Error> {
+    match fs::metadata(dir.join(file_name)) {
+        Ok(metadata) => Ok(Some(metadata)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::Io(e)),
+    }
+}
+
+fn modified(metadata: &Metadata) -> io::Result<Duration> {
+    metadata
+        .modified()?
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_err(io::Error::other)
+}
+
+fn kind_in_bounds(kind: &LineKind, text: &str) -> bool {
+    let in_bounds = |range: &std::ops::Range<usize>| text.get(range.clone());
+    match kind {
+        LineKind::Section { pattern } => in_bounds(pattern).is_some(),
+        LineKind::Pair { key, value } => {
+            in_bounds(key).is_some() && in_bounds(value).is_some()
+        }
+        _ => false,
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Writes little-endian integers, and strings prefixed by their lengths.
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u32(&mut self, n: u32) {
+        self.0.extend(n.to_le_bytes());
+    }
+
+    fn u64(&mut self, n: u64) {
+        self.0.extend(n.to_le_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        self.u32(u32::try_from(len).expect("lengths fit in 32 bits"));
+    }
+
+    fn str(&mut self, s: &str) {
+        self.len(s.len());
+        self.0.extend(s.as_bytes());
+    }
+}
+
+/// Reads what [`Writer`] writes.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        if self.0.len() < n {
+            return Err(invalid_data("truncated snapshot"));
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn len(&mut self) -> io::Result<usize> {
+        Ok(self.u32()? as usize)
+    }
+
+    fn str(&mut self) -> io::Result<&'a str> {
+        let len = self.len()?;
+        std::str::from_utf8(self.take(len)?)
+            .map_err(|_e| invalid_data("invalid unicode"))
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::hash::BuildHasher;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{Behavior, Document, Error, Options, Properties, resolve};
+
+/// Where the EditorConfig files are read from.
+pub trait ConfigSource {
+    /// Reads the file at `path`, or returns `None` if there's no such file.
+    fn read(&self, path: &Path) -> io::Result<Option<String>>;
+}
+
+/// The filesystem, through [`std::fs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FileSystem;
+
+impl ConfigSource for FileSystem {
+    fn read(&self, path: &Path) -> io::Result<Option<String>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                // The EditorConfig file doesn't have to exist at any of the
+                // dirs.
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// The filesystem, except that the EditorConfig files under `root` are read
+/// from the same relative paths under `dir` instead (e.g., a copy of an older
+/// revision of them).
+///
+/// The paths which are looked up are absolute, so `root` should be too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mirror {
+    pub root: PathBuf,
+    pub dir: PathBuf,
+}
+
+impl ConfigSource for Mirror {
+    fn read(&self, path: &Path) -> io::Result<Option<String>> {
+        match path.strip_prefix(&self.root) {
+            Ok(relative) => FileSystem.read(&self.dir.join(relative)),
+            Err(_) => FileSystem.read(path),
+        }
+    }
+}
+
+/// The contents of the EditorConfig files, by their paths.
+impl<S> ConfigSource for HashMap<PathBuf, String, S>
+where
+    S: BuildHasher,
+{
+    fn read(&self, path: &Path) -> io::Result<Option<String>> {
+        Ok(self.get(path).cloned())
+    }
+}
+
+/// The contents of the EditorConfig files, by their paths.
+impl ConfigSource for BTreeMap<PathBuf, String> {
+    fn read(&self, path: &Path) -> io::Result<Option<String>> {
+        Ok(self.get(path).cloned())
+    }
+}
+
+impl<T> ConfigSource for &T
+where
+    T: ConfigSource + ?Sized,
+{
+    fn read(&self, path: &Path) -> io::Result<Option<String>> {
+        (**self).read(path)
+    }
+}
+
+/// Like [`properties_with_options`](crate::properties_with_options), but reads
+/// the EditorConfig files from `source`.
+pub fn properties_from_source<P, S>(
+    path: P,
+    options: Options,
+    source: &S,
+) -> Result<Properties, Error>
+where
+    P: AsRef<Path>,
+    S: ConfigSource + ?Sized,
+{
+    resolve(path.as_ref(), &options, |dir| {
+        load_document(source, dir, &options)
+    })
+}
+
+/// Reads and parses the EditorConfig file at `ec_dir` from `source`, if there
+/// is one.
+pub(crate) fn load_document<S>(
+    source: &S,
+    ec_dir: &Path,
+    options: &Options,
+) -> Result<Option<Document>, Error>
+where
+    S: ConfigSource + ?Sized,
+{
+    let behavior = Behavior::of(options.version);
+    let contents = source.read(&ec_dir.join(options.file_name));
+    Ok(contents
+        .map_err(Error::Io)?
+        .map(|contents| Document::parse_with_behavior(&contents, &behavior)))
+}
@@ -0,0 +1,154 @@
+use std::borrow::Cow;
+
+use crate::{MAX_VERSION, Version};
+
+/// The max. length (in bytes) of a key, when [`Behavior::length_limits`].
+pub const MAX_KEY_LEN: usize = 50;
+/// The max. length (in bytes) of a value, when [`Behavior::length_limits`].
+pub const MAX_VALUE_LEN: usize = 255;
+/// The max. length (in bytes) of a section's glob, when
+/// [`Behavior::length_limits`].
+pub const MAX_SECTION_LEN: usize = 4096;
+
+/// The behaviors of the EditorConfig specification which changed between its
+/// versions, as of a specific version (see [`SPEC_CHANGES`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Behavior {
+    /// `indent_style = tab` implies `indent_size = tab`, and `indent_size =
+    /// tab` takes the value of `tab_width`. Before, `indent_size` was copied
+    /// into `tab_width` whatever its value was.
+    pub tab_indent_size: bool,
+    /// `{num1..num2}` matches the integers from `num1` to `num2`. Before, it
+    /// was matched literally, like any other braces with a single item.
+    pub numeric_ranges: bool,
+    /// A `#` or a `;` following whitespace in a value, or following the `]`
+    /// of a section header, starts a comment, which is stripped from the line.
+    /// Later, comments may only take whole lines.
+    pub inline_comments: bool,
+    /// A `:` separates a key from its value too (whichever of `=` and `:`
+    /// comes first), as the INI parsers of the cores had it. Later, only `=`
+    /// does.
+    pub colon_separators: bool,
+    /// Pairs with keys or values longer than [`MAX_KEY_LEN`] or
+    /// [`MAX_VALUE_LEN`], and sections with globs longer than
+    /// [`MAX_SECTION_LEN`], are ignored (as in the C core).
+    ///
+    /// The lengths are in bytes, as the C core's buffers are, rather than in
+    /// characters (e.g., a value of 128 `é`s is too long).
+    pub length_limits: bool,
+}
+
+/// A change in the behavior of the specification.
+#[derive(Debug, Clone, Copy)]
+pub struct SpecChange {
+    /// The first version with the change.
+    pub version: Version,
+    pub description: &'static str,
+    apply: fn(&mut Behavior),
+}
+
+/// The changes in the behavior of the specification, from the oldest.
+///
+/// The semantics of `**` (matching across directories) and of `unset` aren't
+/// gated, as we don't know of a version in which they were different: they're
+/// the same whatever the version.
+pub const SPEC_CHANGES: &[SpecChange] = &[
+    SpecChange {
+        version: Version::V0_9_0,
+        description: "`indent_style = tab` implies `indent_size = tab`, which \
+                      takes the value of `tab_width`",
+        apply: |b| b.tab_indent_size = true,
+    },
+    SpecChange {
+        version: Version::V0_10_0,
+        description: "`{num1..num2}` matches a range of integers",
+        apply: |b| b.numeric_ranges = true,
+    },
+    SpecChange {
+        version: Version::V0_12_0,
+        description: "pairs with keys longer than 50 bytes or values longer \
+                      than 255 bytes, and sections with globs longer than \
+                      4096 bytes, are ignored",
+        apply: |b| b.length_limits = true,
+    },
+    SpecChange {
+        version: Version::V0_15_0,
+        description: "`#` and `;` only start comments at the start of a line \
+                      (not after a value or a section header)",
+        apply: |b| b.inline_comments = false,
+    },
+    SpecChange {
+        version: Version::V0_15_0,
+        description: "`=` is the only separator of keys and values (`:` was \
+                      one too)",
+        apply: |b| b.colon_separators = false,
+    },
+];
+
+impl Behavior {
+    /// The behavior before any of the [`SPEC_CHANGES`].
+    const ORIGINAL: Self = Self {
+        tab_indent_size: false,
+        numeric_ranges: false,
+        inline_comments: true,
+        colon_separators: true,
+        length_limits: false,
+    };
+
+    /// The behavior as of `version`.
+    pub fn of(version: Version) -> Self {
+        let mut behavior = Self::ORIGINAL;
+        SPEC_CHANGES
+            .iter()
+            .take_while(|change| change.version <= version)
+            .for_each(|change| (change.apply)(&mut behavior));
+        behavior
+    }
+
+    /// Strips the inline comment from the (trimmed) `value` of a pair, if
+    /// there's one and this behavior has them.
+    ///
+    /// With inline comments, `\#` and `\;` are a literal `#` and `;` (as the
+    /// backslash keeps them from following whitespace), and are unescaped.
+    /// Without them, the value is kept as is.
+    pub fn value<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if !self.inline_comments {
+            return Cow::Borrowed(value);
+        }
+
+        let comment = value.char_indices().find(|&(idx, ch)| {
+            matches!(ch, '#' | ';')
+                && value[..idx].ends_with(char::is_whitespace)
+        });
+        let value = match comment {
+            Some((idx, _ch)) => value[..idx].trim_end(),
+            None => value,
+        };
+
+        if value.contains("\\#") || value.contains("\\;") {
+            Cow::Owned(value.replace("\\#", "#").replace("\\;", ";"))
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+
+    /// Whether the pair isn't ignored for its length (`value` is as returned
+    /// by [`Behavior::value`]).
+    pub fn accepts_pair(&self, key: &str, value: &str) -> bool {
+        !self.length_limits
+            || (key.len() <= MAX_KEY_LEN && value.len() <= MAX_VALUE_LEN)
+    }
+
+    /// Whether the section with the glob `pattern` isn't ignored for its
+    /// length.
+    pub fn accepts_section(&self, pattern: &str) -> bool {
+        !self.length_limits || pattern.len() <= MAX_SECTION_LEN
+    }
+}
+
+/// The behavior of [`MAX_VERSION`].
+impl Default for Behavior {
+    fn default() -> Self {
+        Self::of(MAX_VERSION)
+    }
+}
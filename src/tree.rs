@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::{self, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::glob::{self, Glob};
+use crate::source::load_document;
+use crate::walk::{self, Skip};
+use crate::{
+    Behavior, ConfigSource, Document, Error, FileSystem, Options, Properties,
+    ancestors, apply_sections, finish_properties, is_unset, normalize_pair,
+    normalize_path,
+};
+
+/// Trees with fewer files than this are resolved on the calling thread.
+const MIN_PARALLEL_FILES: usize = 256;
+
+/// The number of files a thread takes at a time.
+const CHUNK_SIZE: usize = 64;
+
+/// The options of a tree resolution, built from [`TreeOptions::default`] and
+/// the `with_*` methods (the struct is `#[non_exhaustive]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct TreeOptions<'a> {
+    /// The options of the resolution of each file.
+    pub options: Options<'a>,
+    /// Skip the files ignored by ignore files, e.g., `.gitignore` (defaults to
+    /// `false`). Without the `ignore` feature, ignore files aren't read.
+    pub ignore_files: bool,
+    /// The number of threads to use (defaults to the available parallelism).
+    pub threads: Option<NonZeroUsize>,
+}
+
+impl<'a> TreeOptions<'a> {
+    /// Sets [`TreeOptions::options`].
+    pub fn with_options(self, options: Options<'a>) -> Self {
+        Self { options, ..self }
+    }
+
+    /// Sets [`TreeOptions::ignore_files`].
+    pub fn with_ignore_files(self, ignore_files: bool) -> Self {
+        Self { ignore_files, ..self }
+    }
+
+    /// Sets [`TreeOptions::threads`].
+    pub fn with_threads(self, threads: Option<NonZeroUsize>) -> Self {
+        Self { threads, ..self }
+    }
+}
+
+/// A file and its properties (or the reason they couldn't be resolved).
+pub type TreeEntry = (PathBuf, Result<Properties, Error>);
+
+/// An EditorConfig file with the globs of its sections compiled.
+struct CompiledDocument {
+    document: Document,
+    /// The globs by the index of their section (`None` for the preamble).
+    globs: Vec<Option<Result<Glob, glob::Error>>>,
+}
+
+/// Resolves the properties of every file under `root` (`.git` directories are
+/// always skipped).
+///
+/// Each EditorConfig file is parsed (and its globs compiled) only once, and
+/// large trees are resolved in parallel. The files are sorted by their paths,
+/// which are made absolute (but not canonicalized).
+///
+/// An EditorConfig file which can't be read only fails the files under its
+/// directory, and the directories which can't be read are skipped; only an
+/// unreadable `root` is an error.
+pub fn resolve_tree<P>(
+    root: P,
+    options: TreeOptions,
+) -> Result<Vec<TreeEntry>, Error>
+where
+    P: AsRef<Path>,
+{
+    resolve_tree_from_source(root, options, &FileSystem)
+}
+
+/// Like [`resolve_tree`], but reads the EditorConfig files from `source` (the
+/// files to resolve are still those under `root` in the filesystem).
+pub fn resolve_tree_from_source<P, S>(
+    root: P,
+    options: TreeOptions,
+    source: &S,
+) -> Result<Vec<TreeEntry>, Error>
+where
+    P: AsRef<Path>,
+    S: ConfigSource + ?Sized,
+{
+    let root = path::absolute(root.as_ref()).map_err(Error::Io)?;
+    let skip = Skip { ignored: options.ignore_files, hidden: false };
+    let (mut dirs, files) =
+        walk::walk(&root, skip, |_dir| false).map_err(Error::Io)?;
+
+    // The directories above the root may have EditorConfig files too.
+    dirs.extend(root.ancestors().skip(1).map(Path::to_path_buf));
+
+    let behavior = Behavior::of(options.options.version);
+    let mut documents = HashMap::new();
+    for dir in dirs {
+        if documents.contains_key(&dir) {
+            continue;
+        }
+        let document = load_document(source, &dir, &options.options).and_then(
+            |document| {
+                document
+                    .map(|document| compile(document, &dir, &behavior))
+                    .transpose()
+            },
+        );
+        documents.insert(dir, document);
+    }
+
+    let resolve =
+        |path: &Path| resolve_file(path, &documents, &options.options);
+
+    let threads = options
+        .threads
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+
+    let resolved = if threads <= 1 || files.len() < MIN_PARALLEL_FILES {
+        files.iter().map(|f| resolve(f)).collect()
+    } else {
+        resolve_parallel(&files, threads, resolve)
+    };
+
+    Ok(files.into_iter().zip(resolved).collect())
+}
+
+/// Lists the files under `root` whose properties contain `key` (set to
+/// `value`, if given, or to anything but `unset` otherwise), as resolved by
+/// [`resolve_tree`].
+///
+/// The key and value are normalized like those of the EditorConfig files, so
+/// they match regardless of case where the specification says so. The files
+/// whose properties couldn't be resolved are kept, with their errors, since
+/// the property may or may not apply to them.
+pub fn files_with_property<P>(
+    root: P,
+    key: &str,
+    value: Option<&str>,
+    options: TreeOptions,
+) -> Result<Vec<TreeEntry>, Error>
+where
+    P: AsRef<Path>,
+{
+    let behavior = Behavior::of(options.options.version);
+    let (key, value) = match value {
+        Some(value) => {
+            let (key, value) = normalize_pair(key, value, &behavior);
+            (key, Some(value))
+        }
+        None => (key.to_lowercase(), None),
+    };
+
+    let mut entries = resolve_tree(root, options)?;
+    entries.retain(|(_path, result)| {
+        let Ok(properties) = result else { return true };
+        let found = properties.get(&key);
+        match value.as_ref() {
+            Some(value) => found == Some(value),
+            None => found.is_some_and(|value| !is_unset(value)),
+        }
+    });
+    Ok(entries)
+}
+
+/// Resolves the files on `threads` threads, keeping their order.
+fn resolve_parallel<F>(
+    files: &[PathBuf],
+    threads: usize,
+    resolve: F,
+) -> Vec<Result<Properties, Error>>
+where
+    F: Fn(&Path) -> Result<Properties, Error> + Sync,
+{
+    let next = AtomicUsize::new(0);
+
+    let mut chunks: Vec<(usize, Vec<Result<Properties, Error>>)> =
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut chunks = vec![];
+                        loop {
+                            let start =
+                                next.fetch_add(CHUNK_SIZE, Ordering::Relaxed);
+                            if files.len() <= start {
+                                break chunks;
+                            }
+                            let end = (start + CHUNK_SIZE).min(files.len());
+                            let chunk =
+                                files[start..end].iter().map(|f| resolve(f));
+                            chunks.push((start, chunk.collect()));
+                        }
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|w| {
+                    w.join().unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        });
+
+    chunks.sort_unstable_by_key(|(start, _chunk)| *start);
+    chunks.into_iter().flat_map(|(_start, chunk)| chunk).collect()
+}
+
+fn resolve_file(
+    path: &Path,
+    documents: &HashMap<PathBuf, Result<Option<CompiledDocument>, Error>>,
+    options: &Options,
+) -> Result<Properties, Error> {
+    let normalized_path = normalize_path(path)?;
+    let behavior = Behavior::of(options.version);
+    let mut properties = HashMap::new();
+
+    for dir in ancestors(path) {
+        let compiled = match documents.get(dir) {
+            Some(Ok(Some(compiled))) => compiled,
+            Some(Err(e)) => return Err(copy_error(e)),
+            _ => continue,
+        };
+
+        apply_sections(
+            &compiled.document,
+            &behavior,
+            &mut properties,
+            |idx, _section| match compiled.globs.get(idx) {
+                Some(Some(Ok(glob))) => Ok(glob.is_match(&normalized_path)),
+                Some(Some(Err(_e))) => Err(Error::Parse),
+                _ => Ok(false),
+            },
+        )?;
+    }
+
+    finish_properties(&mut properties, options);
+
+    Ok(properties)
+}
+
+/// A copy of `e`, for each of the files it fails (as `io::Error` isn't
+/// `Clone`).
+fn copy_error(e: &Error) -> Error {
+    match e {
+        Error::Parse => Error::Parse,
+        Error::InvalidPath => Error::InvalidPath,
+        Error::Io(e) => Error::Io(io::Error::new(e.kind(), e.to_string())),
+    }
+}
+
+fn compile(
+    document: Document,
+    dir: &Path,
+    behavior: &Behavior,
+) -> Result<CompiledDocument, Error> {
+    let normalized_dir = normalize_path(dir)?;
+    let globs = document
+        .sections()
+        .map(|section| {
+            section.pattern().map(|pattern| {
+                Glob::with_behavior(&normalized_dir, pattern, behavior)
+            })
+        })
+        .collect();
+    Ok(CompiledDocument { document, globs })
+}
@@ -1,6 +1,8 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::MAX_VERSION;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Version {
     pub major: u32,
@@ -29,16 +31,95 @@ impl Ord for Version {
     }
 }
 
+impl Version {
+    pub const V0_9_0: Self = Self::new(0, 9, 0);
+    pub const V0_10_0: Self = Self::new(0, 10, 0);
+    pub const V0_12_0: Self = Self::new(0, 12, 0);
+    pub const V0_15_0: Self = Self::new(0, 15, 0);
+    pub const V0_17_2: Self = Self::new(0, 17, 2);
+
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Whether this crate supports this version (i.e., it's not greater than
+    /// [`MAX_VERSION`]).
+    pub fn is_supported(&self) -> bool {
+        *self <= MAX_VERSION
+    }
+}
+
+/// Parses one to three dot-separated segments (the missing ones are zeros),
+/// optionally followed by a pre-release (`-`) or a build (`+`) suffix, which
+/// is ignored (e.g., `0.12`, or `1.0.0-beta`).
 impl FromStr for Version {
-    type Err = &'static str;
+    type Err = ParseVersionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        const E_SEG: &str = "expected three dot-separated segments";
-        const E_INT: &str = "expected segments to be unsigned integers";
+        let numbers = match s.find(['-', '+']) {
+            Some(idx) => &s[..idx],
+            None => s,
+        };
+        if numbers.is_empty() {
+            return Err(ParseVersionError::Empty);
+        }
+
+        let mut segs = [0; 3];
+        for (idx, seg) in numbers.split('.').enumerate() {
+            let slot =
+                segs.get_mut(idx).ok_or(ParseVersionError::TooManySegments)?;
+            // `u32::from_str` accepts a leading `+`, which we don't.
+            if !seg.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ParseVersionError::InvalidSegment);
+            }
+            *slot =
+                seg.parse().map_err(|_| ParseVersionError::InvalidSegment)?;
+        }
+
+        let [major, minor, patch] = segs;
+        Ok(Self { major, minor, patch })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseVersionError {
+    Empty,
+    /// There are more than three segments.
+    TooManySegments,
+    /// A segment isn't an unsigned integer.
+    InvalidSegment,
+}
+
+impl Display for ParseVersionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "expected a version",
+            Self::TooManySegments => "expected at most three segments",
+            Self::InvalidSegment => "expected segments to be unsigned integers",
+        })
+    }
+}
+
+impl std::error::Error for ParseVersionError {}
 
-        let mut segs = s.splitn(3, '.');
-        let mut next = || segs.next().ok_or(E_SEG)?.parse().map_err(|_| E_INT);
+#[cfg(feature = "serde")]
+impl serde::Serialize for Version {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
 
-        Ok(Self { major: next()?, minor: next()?, patch: next()? })
+/// Deserialized from the `major.minor.patch` form.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Version {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
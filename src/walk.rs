@@ -0,0 +1,116 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What [`walk`] skips, besides `.git` directories (which are always skipped).
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Skip {
+    /// The files ignored by ignore files (e.g., `.gitignore`).
+    #[cfg_attr(not(feature = "ignore"), allow(dead_code))]
+    pub ignored: bool,
+    /// Hidden files and directories.
+    pub hidden: bool,
+}
+
+/// Recursively collects the directories (including `root`) and the files
+/// (sorted) under `root`, without following symlinks.
+///
+/// Directories for which `skip_dir` returns `true` are not descended into.
+/// Only `root` itself has to be readable: the entries under it which can't be
+/// read (e.g., unreadable directories) are skipped.
+#[cfg(feature = "ignore")]
+pub(crate) fn walk<F>(
+    root: &Path,
+    skip: Skip,
+    skip_dir: F,
+) -> io::Result<(Vec<PathBuf>, Vec<PathBuf>)>
+where
+    F: Fn(&Path) -> bool + Send + Sync + 'static,
+{
+    let walker = ignore::WalkBuilder::new(root)
+        .standard_filters(skip.ignored)
+        .hidden(skip.hidden)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            entry.file_name() != ".git" && !(is_dir && skip_dir(entry.path()))
+        })
+        .build();
+
+    let mut dirs = vec![];
+    let mut files = vec![];
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) if e.depth() == Some(0) => return Err(io::Error::other(e)),
+            Err(_e) => continue,
+        };
+        match entry.file_type() {
+            Some(t) if t.is_dir() => dirs.push(entry.into_path()),
+            Some(t) if t.is_file() => files.push(entry.into_path()),
+            _ => {}
+        }
+    }
+
+    files.sort_unstable();
+    Ok((dirs, files))
+}
+
+/// Recursively collects the directories (including `root`) and the files
+/// (sorted) under `root`, without following symlinks.
+///
+/// Directories for which `skip_dir` returns `true` are not descended into.
+/// Only `root` itself has to be readable: the entries under it which can't be
+/// read (e.g., unreadable directories) are skipped. Without the `ignore`
+/// feature, ignore files aren't read, i.e., `skip.ignored` has no effect.
+#[cfg(not(feature = "ignore"))]
+pub(crate) fn walk<F>(
+    root: &Path,
+    skip: Skip,
+    skip_dir: F,
+) -> io::Result<(Vec<PathBuf>, Vec<PathBuf>)>
+where
+    F: Fn(&Path) -> bool + Send + Sync + 'static,
+{
+    let mut dirs = vec![root.to_path_buf()];
+    let mut files = vec![];
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if dir == root => return Err(e),
+            Err(_e) => continue,
+        };
+
+        for entry in entries {
+            let Ok(entry) = entry else { continue };
+            let name = entry.file_name();
+            let is_hidden = name.as_encoded_bytes().starts_with(b".");
+            if name == ".git" || (skip.hidden && is_hidden) {
+                continue;
+            }
+
+            let Ok(file_type) = entry.file_type() else { continue };
+            let path = entry.path();
+            if file_type.is_dir() {
+                if !skip_dir(&path) {
+                    dirs.push(path.clone());
+                    pending.push(path);
+                }
+            } else if file_type.is_file() {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort_unstable();
+    Ok((dirs, files))
+}
+
+/// Recursively collects the files under `dir`, skipping hidden files and the
+/// files ignored by ignore files (e.g., `.gitignore`).
+pub(crate) fn unignored_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let skip = Skip { ignored: true, hidden: true };
+    walk(dir, skip, |_dir| false).map(|(_dirs, files)| files)
+}
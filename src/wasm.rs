@@ -0,0 +1,38 @@
+//! A JavaScript API, resolving the properties from EditorConfig files given in
+//! memory.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{Options, Version, properties_from_source};
+
+/// Resolves the properties of the file at `path` (a full path).
+///
+/// `configs` maps the full paths of the EditorConfig files (i.e., of the
+/// `.editorconfig` files) to their contents, either as an object or as a
+/// `Map`. The properties are returned as an object. `version` is the
+/// EditorConfig version to use (e.g., `"0.12.0"`, defaults to the latest).
+#[wasm_bindgen(js_name = properties)]
+pub fn properties_js(
+    configs: JsValue,
+    path: &str,
+    version: Option<String>,
+) -> Result<JsValue, JsError> {
+    let configs: HashMap<PathBuf, String> =
+        serde_wasm_bindgen::from_value(configs)?;
+
+    let mut options = Options::default();
+    if let Some(version) = version {
+        options.version = version
+            .parse::<Version>()
+            .map_err(|e| JsError::new(&format!("{version}: {e}")))?;
+    }
+
+    let properties = properties_from_source(path, options, &configs)?;
+
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    Ok(properties.serialize(&serializer)?)
+}
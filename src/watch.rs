@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+
+use crate::{
+    Document, Error, Options, Properties, Version, ancestors, read_document,
+    resolve,
+};
+
+/// The changes in the properties of the watched paths, following a change in
+/// some EditorConfig files.
+#[derive(Debug)]
+pub struct WatchEvent {
+    /// The EditorConfig files which changed (or were created, or removed).
+    pub ec_files: Vec<PathBuf>,
+    /// The watched paths whose properties changed.
+    pub changes: Vec<PropertiesChange>,
+    /// The watched paths whose properties could no longer be resolved (e.g.,
+    /// an EditorConfig file now has an invalid glob). Their properties are
+    /// considered unchanged.
+    pub errors: Vec<(PathBuf, Error)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertiesChange {
+    pub path: PathBuf,
+    pub before: Properties,
+    pub after: Properties,
+}
+
+/// Watches the EditorConfig files relevant to a set of paths, and calls a
+/// handler whenever the properties of any of the paths change.
+///
+/// Only the EditorConfig files which changed are parsed again.
+pub struct Watcher {
+    state: Arc<Mutex<State>>,
+    watcher: RecommendedWatcher,
+}
+
+struct State {
+    file_name: String,
+    version: Version,
+    remove_unset: bool,
+    /// The parsed EditorConfig files, by their directories.
+    documents: HashMap<PathBuf, Option<Document>>,
+    /// The properties of the watched paths.
+    paths: HashMap<PathBuf, Properties>,
+    /// The number of watched paths each watched directory is an ancestor of.
+    dirs: HashMap<PathBuf, usize>,
+}
+
+impl Watcher {
+    /// Creates a watcher, which calls `handler` (from a background thread) with
+    /// the changes.
+    pub fn new<F>(options: Options, mut handler: F) -> Result<Self, Error>
+    where
+        F: FnMut(WatchEvent) + Send + 'static,
+    {
+        let state = Arc::new(Mutex::new(State {
+            file_name: options.file_name.to_owned(),
+            version: options.version,
+            remove_unset: options.remove_unset,
+            documents: HashMap::new(),
+            paths: HashMap::new(),
+            dirs: HashMap::new(),
+        }));
+
+        let handler_state = Arc::clone(&state);
+        let file_name = options.file_name.to_owned();
+
+        let watcher = notify::recommended_watcher(
+            move |event: notify::Result<notify::Event>| {
+                let Ok(event) = event else { return };
+                if matches!(event.kind, EventKind::Access(_)) {
+                    return;
+                }
+
+                let ec_files: Vec<PathBuf> = event
+                    .paths
+                    .into_iter()
+                    .filter(|p| p.file_name() == Some(OsStr::new(&file_name)))
+                    .collect();
+                if ec_files.is_empty() {
+                    return;
+                }
+
+                let event = lock(&handler_state).reload(ec_files);
+                if !event.changes.is_empty() || !event.errors.is_empty() {
+                    handler(event);
+                }
+            },
+        )
+        .map_err(notify_error)?;
+
+        Ok(Self { state, watcher })
+    }
+
+    /// Starts watching `path` (which must be a full path, as for
+    /// [`properties`](crate::properties)), returning its current properties.
+    pub fn watch<P>(&mut self, path: P) -> Result<Properties, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let dirs: Vec<PathBuf> =
+            ancestors(path).map(Path::to_path_buf).collect();
+
+        // The directories are only counted under the lock, and watched once
+        // it's released: `watch` and `unwatch` wait for the event thread of
+        // `notify`, which may be waiting for the lock in the handler.
+        let new_dirs: Vec<PathBuf> = {
+            let mut state = lock(&self.state);
+            if let Some(properties) = state.paths.get(path) {
+                return Ok(properties.clone());
+            }
+
+            dirs.iter()
+                .filter(|&dir| {
+                    let count = state.dirs.entry(dir.clone()).or_default();
+                    *count += 1;
+                    *count == 1
+                })
+                .cloned()
+                .collect()
+        };
+
+        // Watch before reading the EditorConfig files, so we don't miss a
+        // change in between.
+        for dir in new_dirs.iter() {
+            match self.watcher.watch(dir, RecursiveMode::NonRecursive) {
+                Ok(()) => {}
+                // Nothing to watch in a directory which doesn't exist.
+                Err(e) if is_not_found(&e) => {}
+                Err(e) => {
+                    self.release(&dirs);
+                    return Err(notify_error(e));
+                }
+            }
+        }
+
+        let mut state = lock(&self.state);
+        match state.resolve(path) {
+            Ok(properties) => {
+                state.paths.insert(path.to_path_buf(), properties.clone());
+                Ok(properties)
+            }
+            Err(e) => {
+                drop(state);
+                self.release(&dirs);
+                Err(e)
+            }
+        }
+    }
+
+    /// Stops watching `path`.
+    pub fn unwatch<P>(&mut self, path: P)
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if lock(&self.state).paths.remove(path).is_some() {
+            let dirs: Vec<_> = ancestors(path).map(Path::to_path_buf).collect();
+            self.release(&dirs);
+        }
+    }
+
+    /// The current properties of the watched `path`.
+    pub fn properties<P>(&self, path: P) -> Option<Properties>
+    where
+        P: AsRef<Path>,
+    {
+        lock(&self.state).paths.get(path.as_ref()).cloned()
+    }
+
+    /// Stops watching the directories which are no longer needed.
+    fn release(&mut self, dirs: &[PathBuf]) {
+        let unused: Vec<&PathBuf> = {
+            let mut state = lock(&self.state);
+            dirs.iter()
+                .filter(|&dir| {
+                    let Some(count) = state.dirs.get_mut(dir) else {
+                        return false;
+                    };
+                    *count -= 1;
+                    if 0 < *count {
+                        return false;
+                    }
+                    state.dirs.remove(dir);
+                    state.documents.remove(dir);
+                    true
+                })
+                .collect()
+        };
+
+        // Unwatched without the lock, as in `watch`.
+        for dir in unused {
+            let _ = self.watcher.unwatch(dir);
+        }
+    }
+}
+
+impl State {
+    fn options(&self) -> Options<'_> {
+        Options {
+            file_name: &self.file_name,
+            version: self.version,
+            remove_unset: self.remove_unset,
+        }
+    }
+
+    /// Resolves the properties of `path`, parsing only the EditorConfig files
+    /// which aren't parsed yet.
+    fn resolve(&mut self, path: &Path) -> Result<Properties, Error> {
+        for dir in ancestors(path) {
+            if !self.documents.contains_key(dir) {
+                let document = read_document(dir, &self.options())?;
+                self.documents.insert(dir.to_path_buf(), document);
+            }
+        }
+
+        let documents = &self.documents;
+        resolve(path, &self.options(), |dir| {
+            Ok(documents.get(dir).and_then(Option::as_ref))
+        })
+    }
+
+    /// Parses the changed EditorConfig files again, and resolves the properties
+    /// of the paths they affect.
+    fn reload(&mut self, ec_files: Vec<PathBuf>) -> WatchEvent {
+        let mut errors = vec![];
+        let mut dirs = vec![];
+
+        for ec_file in ec_files.iter() {
+            let Some(dir) = ec_file.parent() else { continue };
+            if !self.dirs.contains_key(dir) {
+                // Not relevant to any of the watched paths.
+                continue;
+            }
+
+            match read_document(dir, &self.options()) {
+                Ok(document) => {
+                    self.documents.insert(dir.to_path_buf(), document);
+                }
+                Err(e) => {
+                    // Parse it again on the next change (or resolution).
+                    self.documents.remove(dir);
+                    errors.push((ec_file.clone(), e));
+                }
+            }
+            dirs.push(dir.to_path_buf());
+        }
+
+        let affected: Vec<PathBuf> = self
+            .paths
+            .keys()
+            .filter(|path| ancestors(path).any(|a| dirs.iter().any(|d| a == d)))
+            .cloned()
+            .collect();
+
+        let mut changes = vec![];
+
+        for path in affected {
+            match self.resolve(&path) {
+                Ok(after) => {
+                    let before = self.paths.insert(path.clone(), after.clone());
+                    let before = before.unwrap_or_default();
+                    if before != after {
+                        changes.push(PropertiesChange { path, before, after });
+                    }
+                }
+                Err(e) => errors.push((path, e)),
+            }
+        }
+
+        WatchEvent { ec_files, changes, errors }
+    }
+}
+
+fn lock(state: &Mutex<State>) -> std::sync::MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn is_not_found(e: &notify::Error) -> bool {
+    match &e.kind {
+        notify::ErrorKind::PathNotFound => true,
+        notify::ErrorKind::Io(e) => e.kind() == io::ErrorKind::NotFound,
+        _ => false,
+    }
+}
+
+fn notify_error(e: notify::Error) -> Error {
+    match e.kind {
+        notify::ErrorKind::Io(e) => Error::Io(e),
+        _ => Error::Io(io::Error::other(e)),
+    }
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use editorconfig_core::{
+    AsyncFileSystem, Error, Options, properties, properties_async,
+    properties_with_fs_async,
+};
+
+use common::TempDir;
+
+/// EditorConfig files in memory.
+struct MemoryFileSystem(HashMap<PathBuf, io::Result<String>>);
+
+impl AsyncFileSystem for MemoryFileSystem {
+    fn read_to_string(
+        &self,
+        path: &Path,
+    ) -> impl Future<Output = io::Result<String>> + Send {
+        let result = match self.0.get(path) {
+            Some(Ok(contents)) => Ok(contents.clone()),
+            Some(Err(e)) => Err(io::Error::new(e.kind(), e.to_string())),
+            None => Err(io::ErrorKind::NotFound.into()),
+        };
+        async { result }
+    }
+}
+
+#[tokio::test]
+async fn resolves_like_properties() {
+    let dir = TempDir::new("async");
+    dir.write(".editorconfig", "root = true\n[*]\nindent_style = tab\n");
+    dir.write("src/.editorconfig", "[*.rs]\nindent_size = 4\n");
+    let file = dir.join("src/main.rs");
+
+    let resolved = properties_async(&file).await.unwrap();
+    assert_eq!(resolved, properties(&file).unwrap());
+    assert_eq!(resolved["indent_size"], "4");
+}
+
+#[tokio::test]
+async fn reads_from_any_filesystem() {
+    let fs = MemoryFileSystem(HashMap::from([
+        (
+            PathBuf::from("/repo/.editorconfig"),
+            Ok("root = true\n[*]\nindent_style = tab\n".to_owned()),
+        ),
+        (
+            PathBuf::from("/repo/sub/.editorconfig"),
+            Err(io::ErrorKind::PermissionDenied.into()),
+        ),
+    ]));
+
+    let resolved =
+        properties_with_fs_async("/repo/main.rs", Options::default(), &fs)
+            .await
+            .unwrap();
+    assert_eq!(resolved["indent_style"], "tab");
+
+    // Only a file which doesn't exist is skipped.
+    let result =
+        properties_with_fs_async("/repo/sub/a.rs", Options::default(), &fs)
+            .await;
+    assert!(matches!(
+        result,
+        Err(Error::Io(e)) if e.kind() == io::ErrorKind::PermissionDenied
+    ));
+}
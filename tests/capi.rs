@@ -0,0 +1,188 @@
+//! Tests of the C API, through its symbols (as declared by `editorconfig.h`).
+
+#![cfg(feature = "capi")]
+
+mod common;
+
+use std::env;
+use std::ffi::{CStr, CString, c_char, c_int};
+use std::path::Path;
+use std::process::Command;
+use std::ptr;
+
+// Links the library, which exports the symbols.
+use editorconfig_core as _;
+
+use common::TempDir;
+
+#[allow(non_camel_case_types)]
+type editorconfig_handle = *mut std::ffi::c_void;
+
+unsafe extern "C" {
+    fn editorconfig_handle_init() -> editorconfig_handle;
+    fn editorconfig_handle_destroy(h: editorconfig_handle) -> c_int;
+    fn editorconfig_handle_set_version(
+        h: editorconfig_handle,
+        major: c_int,
+        minor: c_int,
+        patch: c_int,
+    );
+    fn editorconfig_handle_get_err_file(
+        h: editorconfig_handle,
+    ) -> *const c_char;
+    fn editorconfig_handle_get_name_value_count(
+        h: editorconfig_handle,
+    ) -> c_int;
+    fn editorconfig_handle_get_name_value(
+        h: editorconfig_handle,
+        n: c_int,
+        name: *mut *const c_char,
+        value: *mut *const c_char,
+    );
+    fn editorconfig_parse(
+        full_filename: *const c_char,
+        h: editorconfig_handle,
+    ) -> c_int;
+}
+
+/// Parses `path`, returning the result, the properties and the error file.
+fn parse(path: &str, h: editorconfig_handle) -> (c_int, Vec<String>, String) {
+    let path = CString::new(path).unwrap();
+    let string = |s: *const c_char| {
+        // SAFETY: the strings of the handle live until the next parse.
+        (!s.is_null()).then(|| unsafe { CStr::from_ptr(s) }.to_str().unwrap())
+    };
+
+    // SAFETY: `h` is a live handle, and the pointers are valid.
+    unsafe {
+        let result = editorconfig_parse(path.as_ptr(), h);
+
+        let count = editorconfig_handle_get_name_value_count(h);
+        let pairs = (0..count)
+            .map(|n| {
+                let (mut name, mut value) = (ptr::null(), ptr::null());
+                editorconfig_handle_get_name_value(h, n, &mut name, &mut value);
+                format!("{}={}", string(name).unwrap(), string(value).unwrap())
+            })
+            .collect();
+
+        let err_file = editorconfig_handle_get_err_file(h);
+        (
+            result,
+            pairs,
+            string(err_file).unwrap_or_default().to_owned(),
+        )
+    }
+}
+
+#[test]
+fn resolves_sorted_properties() {
+    let dir = TempDir::new("capi");
+    dir.write(".editorconfig", "root = true\n[*]\nindent_style = tab\n");
+    let file = dir.join("a.rs");
+
+    // SAFETY: the handle is destroyed once.
+    let h = unsafe { editorconfig_handle_init() };
+    assert_eq!(
+        parse(file.to_str().unwrap(), h),
+        (
+            0,
+            vec!["indent_size=tab".into(), "indent_style=tab".into()],
+            "".into()
+        ),
+    );
+    // Not a full path.
+    assert_eq!(parse("a.rs", h).0, -2);
+
+    // SAFETY: `h` is a live handle.
+    unsafe { editorconfig_handle_set_version(h, 99, 0, 0) };
+    assert_eq!(parse(file.to_str().unwrap(), h).0, -4);
+
+    assert_eq!(unsafe { editorconfig_handle_destroy(h) }, 0);
+}
+
+#[test]
+fn reports_the_line_of_parse_errors() {
+    let dir = TempDir::new("capi-errors");
+    let malformed =
+        dir.write(".editorconfig", "root = true\n[*]\nindent_style\n");
+    let invalid_glob =
+        dir.write("sub/.editorconfig", "[*]\ncharset = utf-8\n[{3..1}]\n");
+
+    // SAFETY: the handle is destroyed once.
+    let h = unsafe { editorconfig_handle_init() };
+
+    let (result, pairs, err_file) = parse(dir.join("a").to_str().unwrap(), h);
+    assert_eq!((result, pairs), (3, vec![]));
+    assert_eq!(err_file, malformed.to_str().unwrap());
+
+    dir.write(".editorconfig", "root = true\n");
+    let (result, _pairs, err_file) =
+        parse(dir.join("sub/a").to_str().unwrap(), h);
+    assert_eq!(result, 3);
+    assert_eq!(err_file, invalid_glob.to_str().unwrap());
+
+    assert_eq!(unsafe { editorconfig_handle_destroy(h) }, 0);
+}
+
+/// A C program printing the properties of the file given as its argument.
+const PROGRAM: &str = r#"
+#include <stdio.h>
+#include "editorconfig.h"
+
+int main(int argc, char **argv) {
+    editorconfig_handle h = editorconfig_handle_init();
+    int result = editorconfig_parse(argv[1], h);
+    if (result != 0) {
+        fprintf(stderr, "%s\n", editorconfig_get_error_msg(result));
+        return 1;
+    }
+
+    int count = editorconfig_handle_get_name_value_count(h);
+    for (int n = 0; n < count; n++) {
+        const char *name, *value;
+        editorconfig_handle_get_name_value(h, n, &name, &value);
+        printf("%s=%s\n", name, value);
+    }
+    return editorconfig_handle_destroy(h);
+}
+"#;
+
+#[cfg(target_os = "linux")]
+#[test]
+fn links_a_c_program_against_the_header() {
+    let dir = TempDir::new("capi-c");
+    dir.write(".editorconfig", "root = true\n[*.c]\nindent_style = tab\n");
+    let source = dir.write("main.c", PROGRAM);
+    let program = dir.join("main");
+
+    // The `cdylib` is built next to the tests, in `target/<profile>/deps`.
+    let exe = env::current_exe().unwrap();
+    let libs = exe.parent().unwrap();
+    let include = Path::new(env!("CARGO_MANIFEST_DIR")).join("include");
+
+    let status = Command::new(env::var("CC").unwrap_or("cc".into()))
+        .args(["-Wall", "-Werror"])
+        .arg("-I")
+        .arg(include)
+        .arg(&source)
+        .arg("-o")
+        .arg(&program)
+        .arg("-L")
+        .arg(libs)
+        .arg(format!("-Wl,-rpath,{}", libs.display()))
+        .arg("-leditorconfig_core")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    // Cargo's library path could find a `target/<profile>` library built
+    // without the `capi` feature first.
+    let output = Command::new(&program)
+        .arg(&source)
+        .env_remove("LD_LIBRARY_PATH")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(output.stdout, b"indent_size=tab\nindent_style=tab\n");
+}
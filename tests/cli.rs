@@ -0,0 +1,183 @@
+//! Tests of the `editorconfig` binary.
+
+mod common;
+
+use std::fs;
+use std::io::Write as _;
+use std::process::{Command, Output, Stdio};
+
+use common::TempDir;
+
+fn editorconfig(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_editorconfig"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn tree() -> TempDir {
+    let dir = TempDir::new("cli");
+    dir.write(".editorconfig", "root = true\n[*.rs]\nindent_style = tab\n");
+    dir
+}
+
+/// A tree where the EditorConfig file of `bad` can't be read (as it isn't
+/// UTF-8), next to a `good` directory.
+fn tree_with_a_bad_ec_file() -> TempDir {
+    let dir = TempDir::new("cli-bad-file");
+    dir.write(".editorconfig", "root = true\n[*]\nindent_size = 4\n");
+    let bad = dir.write("bad/.editorconfig", "");
+    fs::write(bad, b"[*]\nlabel = \xff\n").unwrap();
+    dir.write("bad/a.rs", "");
+    dir.write("good/.editorconfig", "[*]\nindent_size = 2\n");
+    dir.write("good/b.rs", "");
+    dir
+}
+
+/// The paths under `dir` in the lines of `out`, relative to it.
+fn paths(out: &[u8], dir: &TempDir) -> Vec<String> {
+    let prefix = format!("{}/", dir.path().display());
+    String::from_utf8_lossy(out)
+        .lines()
+        .filter_map(|line| line.split_once(&prefix))
+        .map(|(_, rest)| rest.split([':', ']']).next().unwrap().to_owned())
+        .collect()
+}
+
+#[test]
+fn reads_paths_from_stdin() {
+    let dir = tree();
+    let (a, b) = (dir.join("a.rs"), dir.join("b.rs"));
+    let input = format!("{}\r\n\n{}\n", a.display(), b.display());
+
+    let output = editorconfig(&["--stdin"], input.as_bytes());
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!(
+            "[{}]\nindent_size=tab\nindent_style=tab\n\
+             [{}]\nindent_size=tab\nindent_style=tab\n",
+            a.display(),
+            b.display(),
+        ),
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn fails_only_the_paths_which_are_not_unicode() {
+    let dir = tree();
+    let mut input = dir.join("a.rs").into_os_string().into_encoded_bytes();
+    input.push(0);
+    input.extend(dir.path().as_os_str().as_encoded_bytes());
+    input.extend(b"/\xff.rs\0");
+
+    let output = editorconfig(&["--stdin", "-0"], &input);
+    assert_eq!(output.status.code(), Some(1));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("a.rs]\nindent_size=tab\nindent_style=tab\n"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("path is not valid unicode"), "{stderr}");
+}
+
+#[test]
+fn reports_invalid_export_arguments_as_usage_errors() {
+    let output = editorconfig(&["export", "rustfmt", "/a.rs", "/b.rs"], b"");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Usage:"));
+
+    let output = editorconfig(&["export", "vscode", "/a.rs"], b"");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn tells_output_errors_from_usage_errors() {
+    let dir = tree();
+    let output = Command::new(env!("CARGO_BIN_EXE_editorconfig"))
+        .arg(dir.join("a.rs"))
+        .stdout(std::fs::File::create("/dev/full").unwrap())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(74));
+
+    let output = editorconfig(&["--no-such-flag"], b"");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn documents_the_exit_codes() {
+    let output = editorconfig(&["--help"], b"");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Exit codes:\n  0 "), "{stdout}");
+    assert!(stdout.contains("\n  74 "), "{stdout}");
+}
+
+#[cfg(feature = "ignore")]
+#[test]
+fn lints_the_ec_files_outside_of_git_and_ignored_dirs() {
+    let dir = TempDir::new("cli-lint");
+    dir.write(".editorconfig", "root = true\n[*]\nindent_style = tab\n");
+    dir.write("src/.editorconfig", "[*]\nindent_size = +4\n");
+    dir.write(".gitignore", "target/\n");
+    dir.write("target/.editorconfig", "[*]\nindent_size = +4\n");
+    dir.write(".git/.editorconfig", "[*]\nindent_size = +4\n");
+
+    let path = dir.path().to_str().unwrap();
+    let output = editorconfig(&["lint", path], b"");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout
+            .lines()
+            .map(|l| l.split_once(": ").unwrap().0)
+            .collect::<Vec<_>>(),
+        [format!("{}:2:15", dir.join("src/.editorconfig").display())],
+        "{stdout}",
+    );
+}
+
+#[test]
+fn lists_the_files_with_a_property_despite_a_bad_ec_file() {
+    let dir = tree_with_a_bad_ec_file();
+
+    let path = dir.path().to_str().unwrap();
+    let output = editorconfig(&["where", "indent_size=2", path], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        paths(&output.stdout, &dir),
+        ["good/.editorconfig", "good/b.rs"],
+    );
+    assert_eq!(
+        paths(&output.stderr, &dir),
+        ["bad/.editorconfig", "bad/a.rs"],
+    );
+}
+
+#[test]
+fn diffs_the_properties_despite_a_bad_ec_file() {
+    let dir = tree_with_a_bad_ec_file();
+    let old = TempDir::new("cli-bad-file-old");
+    old.write(".editorconfig", "root = true\n[*]\nindent_size = 4\n");
+    old.write("bad/.editorconfig", "[*]\nlabel = x\n");
+    old.write("good/.editorconfig", "[*]\nindent_size = 3\n");
+
+    let path = dir.path().to_str().unwrap();
+    let old = old.path().to_str().unwrap();
+    let output = editorconfig(&["diff", "--old", old, path], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        paths(&output.stdout, &dir),
+        ["good/.editorconfig", "good/b.rs"],
+    );
+    assert_eq!(
+        paths(&output.stderr, &dir),
+        ["bad/.editorconfig", "bad/a.rs"],
+    );
+}
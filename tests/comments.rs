@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use editorconfig_core::{
+    Behavior, Document, LineKind, MAX_VERSION, Options, Version,
+    properties_from_source,
+};
+
+const WITH_COMMENTS: Version = Version::new(0, 14, 0);
+
+#[test]
+fn strips_inline_comments_before_0_15() {
+    let behavior = Behavior::of(WITH_COMMENTS);
+    let values = [
+        ("4 # four", "4"),
+        ("4 ; four", "4"),
+        ("4\t#four", "4"),
+        // Only after whitespace.
+        ("a#b", "a#b"),
+        ("a;b ;c", "a;b"),
+        // Escaped, and then unescaped.
+        ("a \\# b", "a # b"),
+        ("a \\; b # c", "a ; b"),
+        ("", ""),
+    ];
+    for (value, stripped) in values {
+        assert_eq!(behavior.value(value), stripped, "{value:?}");
+    }
+
+    // Since 0.15.0, values are kept as they are.
+    for (value, _stripped) in values {
+        assert_eq!(Behavior::default().value(value), value, "{value:?}");
+    }
+}
+
+#[test]
+fn keeps_the_raw_values_of_lines() {
+    let document = Document::parse("[*]\nindent_size = 4 # four\n");
+    let line = &document.lines[1];
+
+    assert_eq!(line.raw_value(), Some("4 # four"));
+    assert_eq!(line.pair(), Some(("indent_size", "4 # four")));
+    assert_eq!(line.value(&Behavior::of(WITH_COMMENTS)).unwrap(), "4");
+    assert_eq!(line.value(&Behavior::default()).unwrap(), "4 # four");
+    assert_eq!(document.lines[0].value(&Behavior::default()), None);
+}
+
+#[test]
+fn resolves_values_as_of_the_version() {
+    let configs = HashMap::from([(
+        PathBuf::from("/home/repo/.editorconfig"),
+        "root = true\n[*]\nindent_size = 4 # four\nlabel = a \\# b\n"
+            .to_owned(),
+    )]);
+    let resolve = |version| {
+        let options = Options::default().with_version(version);
+        properties_from_source("/home/repo/a.txt", options, &configs).unwrap()
+    };
+
+    let properties = resolve(WITH_COMMENTS);
+    assert_eq!(properties["indent_size"], "4");
+    assert_eq!(properties["tab_width"], "4");
+    assert_eq!(properties["label"], "a # b");
+
+    let properties = resolve(MAX_VERSION);
+    assert_eq!(properties["indent_size"], "4 # four");
+    assert_eq!(properties["label"], "a \\# b");
+}
+
+#[test]
+fn parses_the_older_comments_and_separators_before_0_15() {
+    let contents = "[*.rs] ; rust\n[*.md]# markdown\nkey: value\n";
+    let behavior = Behavior::of(WITH_COMMENTS);
+
+    let document = Document::parse_with_behavior(contents, &behavior);
+    assert_eq!(document.lines[0].pattern(), Some("*.rs"));
+    assert_eq!(document.lines[1].pattern(), Some("*.md"));
+    assert_eq!(document.lines[2].pair(), Some(("key", "value")));
+    // Whichever separator comes first.
+    let document = Document::parse_with_behavior("a:b=c\nd=e:f\n", &behavior);
+    assert_eq!(document.lines[0].pair(), Some(("a", "b=c")));
+    assert_eq!(document.lines[1].pair(), Some(("d", "e:f")));
+
+    // Since 0.15.0, they're invalid lines.
+    let document = Document::parse(contents);
+    assert!(
+        document.lines.iter().all(|line| line.kind == LineKind::Invalid),
+        "{document:?}",
+    );
+}
+
+#[test]
+fn resolves_the_older_comments_and_separators_before_0_15() {
+    let configs = HashMap::from([(
+        PathBuf::from("/home/repo/.editorconfig"),
+        "root = true\n[*] # all\nindent_style: tab\n".to_owned(),
+    )]);
+    let resolve = |version| {
+        let options = Options::default().with_version(version);
+        properties_from_source("/home/repo/a.txt", options, &configs).unwrap()
+    };
+
+    assert_eq!(resolve(WITH_COMMENTS)["indent_style"], "tab");
+    assert!(resolve(MAX_VERSION).is_empty());
+}
@@ -0,0 +1,77 @@
+//! Helpers shared by the integration tests.
+
+// Each test crate uses only some of them.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A temporary directory, removed when dropped (i.e., even if the test fails).
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// A fresh, empty directory, unique to the test process and `name`.
+    pub fn new(name: &str) -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+        let path = std::env::temp_dir().join(format!(
+            "editorconfig-{name}-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed),
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The absolute path of `relative` in this directory.
+    pub fn join<P: AsRef<Path>>(&self, relative: P) -> PathBuf {
+        self.path.join(relative)
+    }
+
+    /// Writes the file at `relative` (creating its directories), and returns
+    /// its absolute path.
+    pub fn write<P: AsRef<Path>>(
+        &self,
+        relative: P,
+        contents: &str,
+    ) -> PathBuf {
+        let path = self.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    /// Creates the directory `relative`, with directories nested so deep under
+    /// it that they can't be read (as their paths are longer than `PATH_MAX`,
+    /// so even by root), and returns the first of them.
+    #[cfg(target_os = "linux")]
+    pub fn unreadable_dir<P: AsRef<Path>>(&self, relative: P) -> PathBuf {
+        const DEPTH: usize = 20;
+
+        let mut dirs = vec![self.join(relative).join("d")];
+        for _ in 1..DEPTH {
+            dirs.push(dirs.last().unwrap().join("d"));
+        }
+        fs::create_dir_all(dirs.last().unwrap()).unwrap();
+
+        // Renamed from the deepest, so the paths involved stay short.
+        for dir in dirs.iter().rev() {
+            fs::rename(dir, dir.with_file_name("d".repeat(250))).unwrap();
+        }
+        dirs[0].with_file_name("d".repeat(250))
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
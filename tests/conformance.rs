@@ -0,0 +1,426 @@
+//! Runs the [editorconfig-core-test] suite, vendored in
+//! `tests/editorconfig-core-test`, directly against
+//! [`properties_with_options`].
+//!
+//! The expected outputs are read from the suite's `CMakeLists.txt` files. The
+//! `cli` tests exercise the command itself, rather than the library, so they
+//! are left to CTest.
+//!
+//! The vendored snapshot predates version 0.15.0 of the specification (e.g.,
+//! it expects inline comments to be stripped), so it's run at version 0.12.0,
+//! unless a test asks for a specific version. Its files with comments and `:`
+//! separators are also run at [`MAX_VERSION`], with the expectations of 0.15.0.
+//!
+//! [editorconfig-core-test]: https://github.com/editorconfig/editorconfig-core-test
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use editorconfig_core::{
+    MAX_VERSION, Options, Version, properties_with_options,
+};
+
+const SUITE_DIR: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/editorconfig-core-test");
+
+/// The version of the specification the vendored snapshot targets.
+const SUITE_VERSION: Version = Version::V0_12_0;
+
+/// The tests which are expected to fail, with the reasons.
+const KNOWN_FAILURES: &[(&str, &str)] = &[
+    (
+        "octothorpe_in_property",
+        "`test11.c` sets `key=value# not comment`, but the expected value is \
+         `value; not comment`, as copied from the `;` test (fixed in the suite \
+         later on)",
+    ),
+    (
+        "escaped_octothorpe_in_property",
+        "`test12.c` sets `key=value \\# not comment`, which is unescaped to \
+         `value # not comment`, but the expected value is \
+         `value ; not comment`, as copied from the `;` test (fixed in the \
+         suite later on)",
+    ),
+    (
+        "braces_alpha_range1",
+        "the path `{ardvark..antimater}` is meant to match the section \
+         `{aardvark..antelope}` literally (as alphabetical ranges aren't \
+         ranges), but it's misspelled (fixed in the suite later on)",
+    ),
+];
+
+/// The expectations of version 0.15.0 of the specification, which the
+/// snapshot predates, for its files with comments and `:` separators: the
+/// test, the file (under `parser`), the path and the expected pairs.
+///
+/// Since 0.15.0, `#` and `;` only start comments at the start of a line, so
+/// they're kept in values (as are the backslashes before them), and a section
+/// header followed by a comment, or a pair with a `:` separator, is an invalid
+/// line.
+const MAX_VERSION_CASES: &[(&str, &str, &str, &[&str])] = &[
+    ("comments_after_section", "comments.in", "test2.c", &[]),
+    (
+        "comment_before_props",
+        "comments.in",
+        "test3.c",
+        &["key=value"],
+    ),
+    (
+        "comment_between_props",
+        "comments.in",
+        "test4.c",
+        &["key1=value1", "key2=value2"],
+    ),
+    (
+        "semicolon_in_property",
+        "comments.in",
+        "test5.c",
+        &["key=value; not comment"],
+    ),
+    (
+        "escaped_semicolon_in_property",
+        "comments.in",
+        "test6.c",
+        &["key=value \\; not comment"],
+    ),
+    (
+        "octothorpe_comments_after_section",
+        "comments.in",
+        "test8.c",
+        &[],
+    ),
+    (
+        "octothorpe_in_property",
+        "comments.in",
+        "test11.c",
+        &["key=value# not comment"],
+    ),
+    (
+        "escaped_octothorpe_in_property",
+        "comments.in",
+        "test12.c",
+        &["key=value \\# not comment"],
+    ),
+    ("colon_sep_no_whitespace", "whitespace.in", "test1.d", &[]),
+    (
+        "colon_sep_single_spaces_around_equals",
+        "whitespace.in",
+        "test2.d",
+        &[],
+    ),
+    (
+        "colon_sep_multiple_spaces_around_equals",
+        "whitespace.in",
+        "test3.d",
+        &[],
+    ),
+    (
+        "colon_sep_spaces_before_property_name",
+        "whitespace.in",
+        "test4.d",
+        &[],
+    ),
+    (
+        "colon_sep_spaces_after_property_value",
+        "whitespace.in",
+        "test5.d",
+        &[],
+    ),
+];
+
+#[test]
+fn glob() {
+    run("glob");
+}
+
+#[test]
+fn parser() {
+    run("parser");
+}
+
+#[test]
+fn filetree() {
+    run("filetree");
+}
+
+#[test]
+fn properties() {
+    run("properties");
+}
+
+#[test]
+fn comments_and_separators_at_max_version() {
+    let dir = Path::new(SUITE_DIR).join("parser");
+    let failures: Vec<_> = MAX_VERSION_CASES
+        .iter()
+        .filter_map(|&(name, ec_file, path, expected)| {
+            check(&Case {
+                name: name.to_owned(),
+                ec_file: ec_file.to_owned(),
+                path: dir.join(path),
+                expected: expected.iter().map(|&l| l.to_owned()).collect(),
+                version: MAX_VERSION,
+            })
+        })
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+struct Case {
+    name: String,
+    ec_file: String,
+    path: PathBuf,
+    expected: Vec<String>,
+    version: Version,
+}
+
+/// Runs all the tests of the suite's `dir`, and reports all the failures at
+/// once.
+fn run(dir: &str) {
+    let dir = Path::new(SUITE_DIR).join(dir);
+    let cases = read_cases(&dir);
+    assert!(!cases.is_empty(), "no tests in {}", dir.display());
+
+    let failures: Vec<_> = cases
+        .iter()
+        .filter_map(|case| {
+            let known = KNOWN_FAILURES.iter().any(|&(n, _)| n == case.name);
+            match check(case) {
+                Some(_failure) if known => None,
+                None if known => Some(format!(
+                    "{}: passes, so remove it from `KNOWN_FAILURES`\n",
+                    case.name,
+                )),
+                failure => failure,
+            }
+        })
+        .collect();
+    if !failures.is_empty() {
+        panic!(
+            "{} of {} tests failed:\n\n{}",
+            failures.len(),
+            cases.len(),
+            failures.join("\n"),
+        );
+    }
+}
+
+/// Returns a description of the failure, if the case fails.
+fn check(case: &Case) -> Option<String> {
+    let options = Options::default()
+        .with_file_name(&case.ec_file)
+        .with_version(case.version);
+
+    let actual = match properties_with_options(&case.path, options) {
+        Ok(properties) => {
+            let mut pairs: Vec<_> =
+                properties.iter().map(|(k, v)| format!("{k}={v}")).collect();
+            pairs.sort_unstable();
+            pairs
+        }
+        Err(e) => return Some(format!("{}: error: {e}\n", case.name)),
+    };
+
+    if actual == case.expected {
+        return None;
+    }
+
+    let mut report = format!(
+        "{} ({}, -f {} -b {}):\n",
+        case.name,
+        case.path.display(),
+        case.ec_file,
+        case.version,
+    );
+    for line in case.expected.iter().filter(|l| !actual.contains(l)) {
+        report.push_str(&format!("  - {line}\n"));
+    }
+    for line in actual.iter().filter(|l| !case.expected.contains(l)) {
+        report.push_str(&format!("  + {line}\n"));
+    }
+    Some(report)
+}
+
+/// Reads the tests of the `CMakeLists.txt` in `dir`.
+///
+/// Only the little CMake the suite uses is understood: the `new_ec_test*`
+/// functions, `set` and `if(WIN32)` conditions (as on a Unix).
+fn read_cases(dir: &Path) -> Vec<Case> {
+    let cmake_lists = dir.join("CMakeLists.txt");
+    let contents = fs::read_to_string(&cmake_lists).unwrap();
+    let dir_str = dir.to_str().unwrap();
+
+    let mut cases: Vec<Case> = vec![];
+    let mut vars: Vec<(String, String)> = vec![];
+    // Whether each of the enclosing `if`s is taken.
+    let mut conditions: Vec<bool> = vec![];
+
+    for (command, args) in commands(&contents) {
+        let args: Vec<_> = args
+            .into_iter()
+            .map(|arg| {
+                let mut arg =
+                    arg.replace("${CMAKE_CURRENT_SOURCE_DIR}", dir_str);
+                for (name, value) in vars.iter() {
+                    arg = arg.replace(&format!("${{{name}}}"), value);
+                }
+                arg
+            })
+            .collect();
+
+        match command.as_str() {
+            "if" => conditions.push(is_true(&args)),
+            "else" => {
+                let condition = conditions.last_mut().unwrap();
+                *condition = !*condition;
+            }
+            "endif" => {
+                conditions.pop();
+            }
+            _ if conditions.contains(&false) => {}
+            "set" => vars.push((args[0].clone(), args[1].clone())),
+            "new_ec_test"
+            | "new_ec_test_version"
+            | "new_ec_test_full_ec_file_path" => {
+                // CTest unescapes the arguments of the commands once more.
+                let file = args[2].replace("\\\\", "\\");
+                let path = if command == "new_ec_test_full_ec_file_path" {
+                    PathBuf::from(file)
+                } else {
+                    dir.join(file)
+                };
+                let version = match args.get(4) {
+                    Some(version) => version.parse().unwrap(),
+                    None => SUITE_VERSION,
+                };
+                // Tell apart the tests which share a name.
+                let mut name = args[0].clone();
+                let count = cases.iter().filter(|c| c.name == name).count();
+                if count != 0 {
+                    name = format!("{name} ({})", count + 1);
+                }
+
+                cases.push(Case {
+                    name,
+                    ec_file: args[1].clone(),
+                    path,
+                    expected: expected_pairs(&args[3]),
+                    version,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    cases
+}
+
+fn is_true(condition: &[String]) -> bool {
+    match condition {
+        [win32] if win32 == "WIN32" => false,
+        [not, win32] if not == "NOT" && win32 == "WIN32" => true,
+        _ => panic!("unsupported condition: {condition:?}"),
+    }
+}
+
+/// The commands of a CMake file, with their (unescaped) arguments.
+fn commands(contents: &str) -> Vec<(String, Vec<String>)> {
+    let mut commands = vec![];
+    let mut chars = contents.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '#' => {
+                chars.by_ref().find(|&ch| ch == '\n');
+            }
+            ch if ch.is_ascii_alphabetic() || ch == '_' => {
+                let mut name = ch.to_string();
+                while let Some(&ch) = chars.peek()
+                    && (ch.is_ascii_alphanumeric() || ch == '_')
+                {
+                    name.push(ch);
+                    chars.next();
+                }
+                while chars.next_if(|ch| ch.is_whitespace()).is_some() {}
+                assert_eq!(chars.next(), Some('('), "after {name}");
+
+                let mut args = vec![];
+                loop {
+                    match chars.next() {
+                        Some(')') => break,
+                        Some('"') => {
+                            let mut arg = String::new();
+                            while let Some(ch) = chars.next() {
+                                match ch {
+                                    '"' => break,
+                                    '\\' => arg.push(unescape(chars.next())),
+                                    ch => arg.push(ch),
+                                }
+                            }
+                            args.push(arg);
+                        }
+                        Some(ch) if ch.is_whitespace() => {}
+                        Some(ch) => {
+                            let mut arg = String::new();
+                            let mut ch = Some(ch);
+                            while let Some(c) = ch {
+                                match c {
+                                    '\\' => arg.push(unescape(chars.next())),
+                                    c => arg.push(c),
+                                }
+                                ch = chars.next_if(|&ch| {
+                                    !ch.is_whitespace() && ch != ')'
+                                });
+                            }
+                            args.push(arg);
+                        }
+                        None => panic!("unclosed {name}"),
+                    }
+                }
+                commands.push((name, args));
+            }
+            _ => {}
+        }
+    }
+
+    commands
+}
+
+fn unescape(ch: Option<char>) -> char {
+    match ch {
+        Some('t') => '\t',
+        Some('n') => '\n',
+        Some('r') => '\r',
+        Some(ch) => ch,
+        None => panic!("trailing backslash"),
+    }
+}
+
+/// The (sorted) `key=value` lines the output regex of a test expects.
+///
+/// The regexes all take the form of `^line1[ \t]*[\n\r]+line2[ \t\n\r]*$`.
+fn expected_pairs(regex: &str) -> Vec<String> {
+    let body = regex
+        .strip_prefix('^')
+        .and_then(|r| r.strip_suffix("[ \t\n\r]*$"))
+        .or_else(|| regex.strip_prefix('^')?.strip_suffix("[\t\n\r]*$"))
+        .unwrap_or_else(|| panic!("unsupported regex: {regex:?}"));
+
+    let mut pairs: Vec<_> = body
+        .split("[ \t]*[\n\r]+")
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut pair = String::new();
+            let mut chars = line.chars();
+            while let Some(ch) = chars.next() {
+                match ch {
+                    '\\' => pair.extend(chars.next()),
+                    ch => pair.push(ch),
+                }
+            }
+            pair
+        })
+        .collect();
+    pairs.sort_unstable();
+    pairs
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use editorconfig_core::{PropertyChange, TreeOptions, diff_tree};
+
+use common::TempDir;
+
+#[test]
+fn lists_the_changed_properties() {
+    let dir = TempDir::new("diff");
+    for file in ["README.md", "src/lib.rs", "src/main.rs"] {
+        dir.write(file, "");
+    }
+
+    let ec_file = dir.join(".editorconfig");
+    let old = HashMap::from([(
+        ec_file.clone(),
+        "root = true\n[*]\nindent_style = space\n[*.rs]\nindent_size = 4\n"
+            .to_owned(),
+    )]);
+    let new = HashMap::from([(
+        ec_file.clone(),
+        "root = true\n[*]\nindent_style = space\n[*.md]\nmax_line_length = 80\n"
+            .to_owned(),
+    )]);
+
+    let diffs =
+        diff_tree(dir.path(), &old, &new, TreeOptions::default()).unwrap();
+    let diffs: Vec<(PathBuf, Vec<PropertyChange>)> = diffs
+        .into_iter()
+        .map(|(path, changes)| (path, changes.unwrap()))
+        .collect();
+
+    let change =
+        |key: &str, old: Option<&str>, new: Option<&str>| PropertyChange {
+            key: key.to_owned(),
+            old: old.map(str::to_owned),
+            new: new.map(str::to_owned),
+        };
+    let rs_changes = vec![
+        change("indent_size", Some("4"), None),
+        change("tab_width", Some("4"), None),
+    ];
+    assert_eq!(
+        diffs,
+        [
+            (
+                dir.join("README.md"),
+                vec![change("max_line_length", None, Some("80"))],
+            ),
+            (dir.join("src/lib.rs"), rs_changes.clone()),
+            (dir.join("src/main.rs"), rs_changes),
+        ],
+    );
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use editorconfig_core::{Origin, explain, properties};
+
+use common::TempDir;
+
+#[test]
+fn traces_where_each_property_came_from() {
+    let dir = TempDir::new("explain");
+    let outer = dir.write(".editorconfig", "[*]\nindent_size = 8\n");
+    let root = dir.write(
+        "repo/.editorconfig",
+        "root = true\n\n[*]\nindent_size = 2\n\n[*.md]\ncharset = utf-8\n",
+    );
+    let nested = dir.write(
+        "repo/src/.editorconfig",
+        "[*.rs]\nindent_style = space\nindent_size = 4\n",
+    );
+    let file = dir.join("repo/src/main.rs");
+
+    let explanation = explain(&file).unwrap();
+    assert_eq!(explanation.properties, properties(&file).unwrap());
+
+    let paths: Vec<_> = explanation.files.iter().map(|f| &f.path).collect();
+    assert_eq!(paths, [&outer, &root, &nested]);
+    // The root below discards the file above it.
+    assert!(explanation.files[0].discarded);
+    assert_eq!(explanation.files[1].root, Some(1));
+    assert!(!explanation.files[1].discarded);
+
+    let sections = &explanation.files[1].sections;
+    assert_eq!((sections[0].line, sections[0].is_match), (3, true));
+    assert_eq!((sections[1].line, sections[1].is_match), (6, false));
+    assert!(sections[1].pairs.is_empty());
+
+    // `indent_size = 2` is overridden by the nested file.
+    let pair = &sections[0].pairs[0];
+    assert_eq!(
+        (pair.key.as_str(), pair.value.as_str()),
+        ("indent_size", "2")
+    );
+    assert_eq!(
+        pair.overridden_by,
+        Some(Origin::Pair { path: nested.clone(), line: 3 }),
+    );
+
+    assert_eq!(
+        explanation.origins["indent_size"],
+        Origin::Pair { path: nested.clone(), line: 3 },
+    );
+    assert_eq!(
+        explanation.origins["indent_style"],
+        Origin::Pair { path: nested, line: 2 },
+    );
+    // Not set by any pair, but derived from `indent_size`.
+    assert_eq!(explanation.properties["tab_width"], "4");
+    assert_eq!(explanation.origins["tab_width"], Origin::Derived);
+}
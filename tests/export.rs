@@ -0,0 +1,54 @@
+use editorconfig_core::{
+    Properties, clang_format_config, prettier_config, rustfmt_config,
+    vscode_settings,
+};
+
+fn properties(pairs: &[(&str, &str)]) -> Properties {
+    pairs.iter().map(|&(k, v)| (k.to_owned(), v.to_owned())).collect()
+}
+
+#[test]
+fn converts_to_each_format() {
+    let rust = properties(&[
+        ("indent_style", "space"),
+        ("indent_size", "4"),
+        ("tab_width", "4"),
+        ("end_of_line", "crlf"),
+        ("max_line_length", "80"),
+        ("insert_final_newline", "true"),
+        // Left out, like invalid values.
+        ("charset", "unset"),
+        ("trim_trailing_whitespace", "maybe"),
+    ]);
+
+    assert_eq!(
+        rustfmt_config(&rust),
+        "hard_tabs = false\ntab_spaces = 4\nmax_width = 80\n\
+         newline_style = \"Windows\"\n",
+    );
+    assert_eq!(
+        prettier_config(&rust),
+        "{\n  \"useTabs\": false,\n  \"tabWidth\": 4,\n  \"printWidth\": 80,\n  \
+         \"endOfLine\": \"crlf\"\n}\n",
+    );
+    assert_eq!(
+        clang_format_config(&rust),
+        "UseTab: Never\nIndentWidth: 4\nTabWidth: 4\nColumnLimit: 80\n\
+         LineEnding: CRLF\nInsertNewlineAtEOF: true\n",
+    );
+
+    let markdown =
+        properties(&[("indent_style", "tab"), ("max_line_length", "off")]);
+    assert_eq!(
+        clang_format_config(&markdown),
+        "UseTab: ForIndentation\nColumnLimit: 0\n",
+    );
+    assert_eq!(
+        vscode_settings([("rust", &rust), ("markdown", &markdown)]),
+        "{\n  \"[rust]\": {\n    \"editor.insertSpaces\": true,\n    \
+         \"editor.tabSize\": 4,\n    \"editor.indentSize\": 4,\n    \
+         \"editor.rulers\": [80],\n    \"files.eol\": \"\\r\\n\",\n    \
+         \"files.insertFinalNewline\": true\n  },\n  \"[markdown]\": {\n    \
+         \"editor.insertSpaces\": false\n  }\n}\n",
+    );
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use std::process::Command;
+
+use editorconfig_core::{FormatOptions, format};
+
+use common::TempDir;
+
+const MESSY: &str = "  ; top comment\nroot=true\n\n\n# about rust\n[*.rs]\n\
+                     Indent_Style=TAB\n; why\ncharset   =   utf-8\n\n\n\
+                     end_of_line=LF\n[*.md]\nfoo = Bar  \n";
+
+#[test]
+fn formats_canonically() {
+    assert_eq!(
+        format(MESSY, FormatOptions::default()),
+        "# top comment\nroot = true\n\n# about rust\n[*.rs]\n\
+         indent_style = tab\n# why\ncharset = utf-8\n\nend_of_line = lf\n\n\
+         [*.md]\nfoo = Bar\n",
+    );
+}
+
+#[test]
+fn sorts_keys_with_their_comments() {
+    let options =
+        FormatOptions::default().with_sort_keys(true).with_comment_marker(';');
+    assert_eq!(
+        format(MESSY, options),
+        "; top comment\nroot = true\n\n; about rust\n[*.rs]\n\
+         ; why\ncharset = utf-8\nend_of_line = lf\nindent_style = tab\n\n\
+         [*.md]\nfoo = Bar\n",
+    );
+
+    // A comment which isn't attached to a pair splits the sorting.
+    assert_eq!(
+        format("[*]\nb = 1\na = 2\n\n# group\n\nd = 1\nc = 2\n", options),
+        "[*]\na = 2\nb = 1\n\n; group\n\nc = 2\nd = 1\n",
+    );
+}
+
+#[test]
+fn is_idempotent() {
+    for options in [
+        FormatOptions::default(),
+        FormatOptions::default().with_sort_keys(true).with_comment_marker(';'),
+    ] {
+        let formatted = format(MESSY, options);
+        assert_eq!(format(&formatted, options), formatted);
+    }
+}
+
+#[test]
+fn checks_and_writes_files() {
+    let dir = TempDir::new("fmt");
+    let messy = dir.write(".editorconfig", MESSY);
+    let formatted = dir.write(
+        "src/.editorconfig",
+        &format(MESSY, FormatOptions::default()),
+    );
+    let fmt = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_editorconfig"))
+            .arg("fmt")
+            .args(args)
+            .arg(dir.path())
+            .output()
+            .unwrap()
+    };
+
+    let output = fmt(&["--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}\n", messy.display()),
+    );
+
+    assert!(fmt(&[]).status.success());
+    assert!(fmt(&["--check"]).status.success());
+    assert_eq!(
+        std::fs::read_to_string(&messy).unwrap(),
+        std::fs::read_to_string(&formatted).unwrap(),
+    );
+}
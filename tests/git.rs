@@ -0,0 +1,80 @@
+#![cfg(feature = "git")]
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use editorconfig_core::{GitTree, Options, properties_from_source};
+use git2::{Oid, Repository, Signature};
+
+use common::TempDir;
+
+/// Commits the files (with their contents) on top of `HEAD`.
+fn commit(repo: &Repository, files: &[(&str, &str)], message: &str) -> Oid {
+    let workdir = repo.workdir().unwrap();
+    let mut index = repo.index().unwrap();
+    for (path, contents) in files {
+        let full_path = workdir.join(path);
+        fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+        fs::write(&full_path, contents).unwrap();
+        index.add_path(Path::new(path)).unwrap();
+    }
+    index.write().unwrap();
+
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::now("Test", "test@example.com").unwrap();
+    let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+    let parents: Vec<_> = parent.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )
+    .unwrap()
+}
+
+#[test]
+fn resolves_against_commits() {
+    let dir = TempDir::new("git");
+    let repo = Repository::init(dir.path()).unwrap();
+
+    let first = commit(
+        &repo,
+        &[
+            (".editorconfig", "root = true\n[*]\nindent_style = space\n"),
+            ("src/.editorconfig", "[*.rs]\nindent_size = 4\n"),
+            ("src/main.rs", ""),
+        ],
+        "First",
+    );
+    commit(
+        &repo,
+        &[("src/.editorconfig", "[*.rs]\nindent_size = 2\n")],
+        "Second",
+    );
+
+    // Changes in the working tree don't matter.
+    dir.write(".editorconfig", "root = true\n[*]\nindent_style = tab\n");
+    fs::remove_file(dir.join("src/.editorconfig")).unwrap();
+
+    let workdir = repo.workdir().unwrap().to_path_buf();
+    let file = workdir.join("src/main.rs");
+    let resolve = |rev: &str| {
+        let source = GitTree::new(&repo, rev).unwrap();
+        properties_from_source(&file, Options::default(), &source).unwrap()
+    };
+
+    let head = resolve("HEAD");
+    assert_eq!(head["indent_style"], "space");
+    assert_eq!(head["indent_size"], "2");
+
+    let first = resolve(&first.to_string());
+    assert_eq!(first["indent_style"], "space");
+    assert_eq!(first["indent_size"], "4");
+
+    assert!(GitTree::new(&repo, "no-such-branch").is_err());
+}
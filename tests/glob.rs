@@ -0,0 +1,288 @@
+//! Differential tests of the section globs, against a straightforward
+//! (backtracking) matcher of the EditorConfig glob syntax.
+//!
+//! The patterns are generated as syntax trees, which the reference matcher
+//! matches directly, while the core gets them rendered into a section of an
+//! EditorConfig file.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use editorconfig_core::{Options, properties_from_source};
+use proptest::prelude::*;
+
+/// The directory of the EditorConfig file.
+const EC_DIR: &str = "/ec";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(char),
+    /// `?`
+    Any,
+    /// `*`
+    Star,
+    /// `**`
+    StarStar,
+    /// `[...]` or `[!...]`
+    Class {
+        negated: bool,
+        items: Vec<(char, char)>,
+    },
+    /// `{s1,s2,...}`
+    Alternatives(Vec<Vec<Token>>),
+    /// `{num1..num2}`
+    Range(i32, i32),
+}
+
+fn render(tokens: &[Token]) -> String {
+    let mut pattern = String::new();
+    for token in tokens {
+        match token {
+            Token::Literal(ch) => pattern.push(*ch),
+            Token::Any => pattern.push('?'),
+            Token::Star => pattern.push('*'),
+            Token::StarStar => pattern.push_str("**"),
+            Token::Class { negated, items } => {
+                pattern.push('[');
+                if *negated {
+                    pattern.push('!');
+                }
+                for &(start, end) in items {
+                    pattern.push(start);
+                    if start != end {
+                        pattern.push('-');
+                        pattern.push(end);
+                    }
+                }
+                pattern.push(']');
+            }
+            Token::Alternatives(alternatives) => {
+                let alternatives: Vec<_> =
+                    alternatives.iter().map(|a| render(a)).collect();
+                pattern.push('{');
+                pattern.push_str(&alternatives.join(","));
+                pattern.push('}');
+            }
+            Token::Range(start, end) => {
+                pattern.push_str(&format!("{{{start}..{end}}}"));
+            }
+        }
+    }
+    pattern
+}
+
+/// Whether `pattern` (in the EditorConfig file at [`EC_DIR`]) matches the file
+/// at `path` (relative to [`EC_DIR`]).
+fn reference_is_match(pattern: &[Token], path: &str) -> bool {
+    let rendered = render(pattern);
+    let full_path: Vec<char> = format!("/{path}").chars().collect();
+
+    if rendered.starts_with('/') {
+        ends(pattern, &full_path, 0).contains(&full_path.len())
+    } else if rendered.contains('/') {
+        ends(pattern, &full_path, 1).contains(&full_path.len())
+    } else {
+        // A glob without a path separator matches files in any subdirectory.
+        (0..full_path.len()).filter(|&i| full_path[i] == '/').any(|i| {
+            ends(pattern, &full_path, i + 1).contains(&full_path.len())
+        })
+    }
+}
+
+/// All the positions in `input` at which matching `tokens` from `pos` may end.
+fn ends(tokens: &[Token], input: &[char], pos: usize) -> Vec<usize> {
+    let Some((token, rest)) = tokens.split_first() else { return vec![pos] };
+
+    // `/**/` matches zero or more directories.
+    if let [Token::StarStar, Token::Literal('/'), after @ ..] = rest
+        && *token == Token::Literal('/')
+    {
+        let mut all = vec![];
+        if input.get(pos) == Some(&'/') {
+            all.extend(ends(after, input, pos + 1));
+            for end in pos + 1..input.len() {
+                if input[end] == '/' {
+                    all.extend(ends(after, input, end + 1));
+                }
+            }
+        }
+        return all;
+    }
+
+    let token_ends: Vec<usize> = match token {
+        Token::Literal(ch) => {
+            if input.get(pos) == Some(ch) {
+                vec![pos + 1]
+            } else {
+                vec![]
+            }
+        }
+        Token::Any => match input.get(pos) {
+            Some(&ch) if ch != '/' => vec![pos + 1],
+            _ => vec![],
+        },
+        Token::Star => {
+            let len = input[pos..].iter().take_while(|&&ch| ch != '/').count();
+            (pos..=pos + len).collect()
+        }
+        Token::StarStar => (pos..=input.len()).collect(),
+        Token::Class { negated, items } => match input.get(pos) {
+            Some(&ch)
+                if items.iter().any(|&(s, e)| (s..=e).contains(&ch))
+                    != *negated =>
+            {
+                vec![pos + 1]
+            }
+            _ => vec![],
+        },
+        Token::Alternatives(alternatives) => alternatives
+            .iter()
+            .flat_map(|alternative| ends(alternative, input, pos))
+            .collect(),
+        Token::Range(start, end) => (pos + 1..=input.len())
+            .filter(|&end_pos| {
+                let s: String = input[pos..end_pos].iter().collect();
+                is_integer(&s)
+                    && s.parse::<i64>().is_ok_and(|n| {
+                        (i64::from(*start)..=i64::from(*end)).contains(&n)
+                    })
+            })
+            .collect(),
+    };
+
+    let mut all: Vec<_> =
+        token_ends.into_iter().flat_map(|end| ends(rest, input, end)).collect();
+    all.sort_unstable();
+    all.dedup();
+    all
+}
+
+/// Whether `s` is an integer with an optional sign, and no leading zeros.
+fn is_integer(s: &str) -> bool {
+    let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+    !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'))
+}
+
+fn core_is_match(pattern: &str, path: &str) -> bool {
+    let configs = HashMap::from([(
+        PathBuf::from(format!("{EC_DIR}/.editorconfig")),
+        format!("root = true\n[{pattern}]\nmatched = true\n"),
+    )]);
+    let properties = properties_from_source(
+        format!("{EC_DIR}/{path}"),
+        Options::default(),
+        &configs,
+    )
+    .unwrap();
+    properties.contains_key("matched")
+}
+
+fn token() -> impl Strategy<Value = Token> {
+    let class_item = prop_oneof![
+        prop::char::ranges(vec!['a'..='c', '0'..='2'].into())
+            .prop_map(|ch| (ch, ch)),
+        Just(('a', 'b')),
+        Just(('0', '1')),
+    ];
+    let leaf = prop_oneof![
+        4 => prop::sample::select(vec!['a', 'b', '0', '1', '-', '.', '/'])
+            .prop_map(Token::Literal),
+        1 => Just(Token::Any),
+        1 => Just(Token::Star),
+        1 => Just(Token::StarStar),
+        1 => (any::<bool>(), prop::collection::vec(class_item, 1..3))
+            .prop_map(|(negated, items)| Token::Class { negated, items }),
+        1 => (-12..12, 1..15)
+            .prop_map(|(start, len)| Token::Range(start, start + len)),
+    ];
+
+    leaf.prop_recursive(2, 16, 3, |inner| {
+        prop::collection::vec(prop::collection::vec(inner, 0..3), 2..4)
+            .prop_map(Token::Alternatives)
+    })
+}
+
+fn pattern() -> impl Strategy<Value = Vec<Token>> {
+    prop::collection::vec(token(), 1..6)
+        .prop_filter("adjacent stars are a different wildcard", |tokens| {
+            !has_adjacent_stars(tokens)
+        })
+}
+
+fn has_adjacent_stars(tokens: &[Token]) -> bool {
+    let is_star = |t: &Token| matches!(t, Token::Star | Token::StarStar);
+
+    tokens.windows(2).any(|pair| pair.iter().all(is_star))
+        || tokens.iter().any(|token| match token {
+            Token::Alternatives(alternatives) => {
+                alternatives.iter().any(|a| has_adjacent_stars(a))
+            }
+            _ => false,
+        })
+}
+
+fn path() -> impl Strategy<Value = String> {
+    let component = "[ab01+.-]{1,4}"
+        .prop_filter("not a special directory", |c| c != "." && c != "..");
+    prop::collection::vec(component, 1..4).prop_map(|c| c.join("/"))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn matches_like_the_reference(pattern in pattern(), path in path()) {
+        let rendered = render(&pattern);
+        prop_assert_eq!(
+            core_is_match(&rendered, &path),
+            reference_is_match(&pattern, &path),
+            "[{}] on {}",
+            rendered,
+            path,
+        );
+    }
+
+    #[test]
+    fn never_panics(pattern in "[ab/*?!,.{}\\[\\]\\\\0-9-]{0,12}", path in path()) {
+        let configs = HashMap::from([(
+            PathBuf::from(format!("{EC_DIR}/.editorconfig")),
+            format!("[{pattern}]\nk = v\n"),
+        )]);
+        let _ = properties_from_source(
+            format!("{EC_DIR}/{path}"),
+            Options::default(),
+            &configs,
+        );
+    }
+}
+
+proptest! {
+    // Wide ranges are slower to compile.
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    #[test]
+    fn matches_numeric_ranges_exactly(
+        a in any::<i32>(),
+        b in any::<i32>(),
+        offset in -1000_i64..1000,
+        near_end in any::<bool>(),
+    ) {
+        let (start, end) = (a.min(b), a.max(b));
+        prop_assume!(start < end);
+
+        let bound = if near_end { end } else { start };
+        let n = i64::from(bound) + offset;
+        let pattern = [Token::Range(start, end)];
+        let path = n.to_string();
+        prop_assert_eq!(
+            core_is_match(&render(&pattern), &path),
+            reference_is_match(&pattern, &path),
+            "{{{}..{}}} on {}",
+            start,
+            end,
+            path,
+        );
+    }
+}
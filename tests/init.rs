@@ -0,0 +1,106 @@
+mod common;
+
+use editorconfig_core::{infer, scaffold};
+
+use common::TempDir;
+
+const TABS: &str = "fn a() {\n\tb();\n}\n";
+
+fn tree() -> TempDir {
+    let dir = TempDir::new("init");
+    dir.write("src/a.rs", "fn a() {\n    b();\n}\n");
+    dir.write("src/b.rs", "fn b() {\n    if c {\n        d();\n    }\n}\n");
+    dir.write("Makefile", "all:\n\techo all\n");
+    dir.write("notes.txt", "a  \r\nb\r\n");
+    dir.write("data.bin", "\0\u{1}\0");
+    dir.write("empty.rs", "");
+    // Hidden and ignored files would outvote the others.
+    dir.write(".hidden/a.rs", TABS);
+    dir.write(".hidden/b.rs", TABS);
+    if cfg!(feature = "ignore") {
+        dir.write(".gitignore", "target/\n");
+        dir.write("target/a.rs", TABS);
+        dir.write("target/b.rs", TABS);
+    }
+    dir
+}
+
+#[test]
+fn infers_the_conventions_of_each_extension() {
+    let dir = tree();
+    let conventions = infer(dir.path()).unwrap();
+
+    let sorted = |pattern: &str| {
+        let mut pairs: Vec<_> = conventions[pattern]
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect();
+        pairs.sort();
+        pairs
+    };
+
+    assert_eq!(
+        conventions.keys().collect::<Vec<_>>(),
+        ["*.rs", "*.txt", "Makefile"],
+    );
+    assert_eq!(
+        sorted("*.rs"),
+        [
+            "charset=utf-8",
+            "end_of_line=lf",
+            "indent_size=4",
+            "indent_style=space",
+            "insert_final_newline=true",
+            "trim_trailing_whitespace=true",
+        ],
+    );
+    assert_eq!(
+        sorted("*.txt"),
+        [
+            "charset=utf-8",
+            "end_of_line=crlf",
+            "insert_final_newline=true",
+            "trim_trailing_whitespace=false",
+        ],
+    );
+    assert_eq!(
+        sorted("Makefile"),
+        [
+            "charset=utf-8",
+            "end_of_line=lf",
+            "indent_style=tab",
+            "insert_final_newline=true",
+            "trim_trailing_whitespace=true",
+        ],
+    );
+}
+
+#[test]
+fn scaffolds_a_root_file_with_the_common_pairs_first() {
+    let dir = tree();
+    let conventions = infer(dir.path()).unwrap();
+
+    assert_eq!(
+        scaffold(&conventions),
+        "root = true\n\n\
+         [*]\ncharset = utf-8\ninsert_final_newline = true\n\n\
+         [*.rs]\nindent_style = space\nindent_size = 4\nend_of_line = lf\n\
+         trim_trailing_whitespace = true\n\n\
+         [*.txt]\nend_of_line = crlf\ntrim_trailing_whitespace = false\n\n\
+         [Makefile]\nindent_style = tab\nend_of_line = lf\n\
+         trim_trailing_whitespace = true\n",
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn skips_the_directories_which_cant_be_read() {
+    let dir = tree();
+    let expected = infer(dir.path()).unwrap();
+
+    dir.unreadable_dir("build");
+    assert_eq!(infer(dir.path()).unwrap(), expected);
+
+    // But the root has to be readable.
+    assert!(infer(dir.join("missing")).is_err());
+}
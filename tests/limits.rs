@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use editorconfig_core::{
+    Behavior, Document, MAX_KEY_LEN, MAX_SECTION_LEN, MAX_VALUE_LEN,
+    MAX_VERSION, Options, Properties, Rule, Version, lint, lint_with_behavior,
+    properties_from_source,
+};
+
+const BEFORE_LIMITS: Version = Version::new(0, 11, 0);
+
+fn resolve(contents: &str, version: Version) -> Properties {
+    let configs = HashMap::from([(
+        PathBuf::from("/home/repo/.editorconfig"),
+        format!("root = true\n{contents}"),
+    )]);
+    let options = Options::default().with_version(version);
+    properties_from_source("/home/repo/a.txt", options, &configs).unwrap()
+}
+
+#[test]
+fn ignores_the_pairs_and_sections_which_are_too_long() {
+    let key = "k".repeat(MAX_KEY_LEN);
+    let long_key = "k".repeat(MAX_KEY_LEN + 1);
+    let value = "v".repeat(MAX_VALUE_LEN);
+    let long_value = "v".repeat(MAX_VALUE_LEN + 1);
+    let contents = format!(
+        "[*]\n{key} = 1\n{long_key} = 1\na = {value}\nb = {long_value}\n"
+    );
+
+    let properties = resolve(&contents, Version::V0_12_0);
+    assert!(properties.contains_key(&key));
+    assert!(!properties.contains_key(&long_key));
+    assert_eq!(properties["a"], value);
+    assert!(!properties.contains_key("b"));
+
+    // Before 0.12.0, there are no limits.
+    let properties = resolve(&contents, BEFORE_LIMITS);
+    assert!(properties.contains_key(&long_key));
+    assert_eq!(properties["b"], long_value);
+
+    let glob = format!("{{*.txt,{}}}", "x".repeat(MAX_SECTION_LEN));
+    let contents = format!("[{glob}]\na = 1\n");
+    assert!(!resolve(&contents, Version::V0_12_0).contains_key("a"));
+    assert!(resolve(&contents, BEFORE_LIMITS).contains_key("a"));
+}
+
+#[test]
+fn counts_bytes_rather_than_characters() {
+    // 128 characters, but 256 bytes.
+    let value = "é".repeat(128);
+    let properties = resolve(&format!("[*]\na = {value}\n"), MAX_VERSION);
+    assert!(!properties.contains_key("a"));
+}
+
+#[test]
+fn reports_what_is_ignored_as_of_the_version() {
+    let contents = format!(
+        "[{}]\n[*]\n{} = 1\na = {}\n",
+        "a".repeat(MAX_SECTION_LEN + 1),
+        "k".repeat(MAX_KEY_LEN + 1),
+        "é".repeat(128),
+    );
+    let document = Document::parse(&contents);
+
+    let diagnostics = lint(&document);
+    let too_long: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.rule == Rule::TooLong)
+        .map(|d| (d.line, d.column))
+        .collect();
+    assert_eq!(too_long, [(1, 1), (3, 1), (4, 5)]);
+
+    let diagnostics =
+        lint_with_behavior(&document, &Behavior::of(BEFORE_LIMITS));
+    assert!(diagnostics.iter().all(|d| d.rule != Rule::TooLong));
+}
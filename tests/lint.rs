@@ -0,0 +1,79 @@
+mod common;
+
+use editorconfig_core::{
+    Behavior, Document, Options, Rule, Version, lint, lint_file,
+    lint_with_behavior,
+};
+
+use common::TempDir;
+
+fn rules(diagnostics: &[editorconfig_core::Diagnostic]) -> Vec<(usize, Rule)> {
+    diagnostics.iter().map(|d| (d.line, d.rule)).collect()
+}
+
+#[test]
+fn checks_values_as_the_version_reads_them() {
+    let document = Document::parse("[*]\nindent_size = 4 # four\n");
+
+    // The inline comment is part of the value since 0.15.0.
+    let diagnostics = lint(&document);
+    assert_eq!(rules(&diagnostics), [(2, Rule::InvalidValue)]);
+
+    let diagnostics =
+        lint_with_behavior(&document, &Behavior::of(Version::V0_12_0));
+    assert_eq!(diagnostics, []);
+}
+
+#[test]
+fn checks_integers_are_only_digits() {
+    let document = Document::parse(
+        "[*]\nindent_size = +4\ntab_width = 0\nmax_line_length = 80\n\
+         indent_size = 4\n",
+    );
+    let diagnostics = lint(&document);
+    let invalid: Vec<_> = rules(&diagnostics)
+        .into_iter()
+        .filter(|(_line, rule)| *rule == Rule::InvalidValue)
+        .collect();
+    assert_eq!(invalid, [(2, Rule::InvalidValue), (3, Rule::InvalidValue)]);
+}
+
+#[test]
+fn lints_files_as_of_the_version() {
+    let dir = TempDir::new("lint-version");
+    let ec_file = dir.write(".editorconfig", "[*]\nindent_size = 4 ; four\n");
+    dir.write("a.txt", "");
+
+    let diagnostics = lint_file(&ec_file, Options::default()).unwrap();
+    assert_eq!(rules(&diagnostics), [(2, Rule::InvalidValue)]);
+
+    let options = Options::default().with_version(Version::V0_12_0);
+    assert_eq!(lint_file(&ec_file, options).unwrap(), []);
+}
+
+#[cfg(feature = "ignore")]
+#[test]
+fn skips_git_ignored_and_nested_root_files() {
+    let dir = TempDir::new("lint-walk");
+    let ec_file = dir.write(
+        ".editorconfig",
+        "root = true\n[*.rs]\nindent_style = tab\n[*.o]\nindent_style = tab\n\
+         [*.md]\nindent_style = tab\n[*.txt]\nindent_style = tab\n",
+    );
+    dir.write(".gitignore", "target/\n");
+    dir.write("src/main.rs", "");
+    dir.write("target/main.o", "");
+    dir.write(".git/README.md", "");
+    dir.write("nested/.editorconfig", "root = true\n");
+    dir.write("nested/a.txt", "");
+
+    let diagnostics = lint_file(&ec_file, Options::default()).unwrap();
+    assert_eq!(
+        rules(&diagnostics),
+        [
+            (4, Rule::UnmatchedSection),
+            (6, Rule::UnmatchedSection),
+            (8, Rule::UnmatchedSection),
+        ],
+    );
+}
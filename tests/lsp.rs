@@ -0,0 +1,194 @@
+//! Tests of the `editorconfig-lsp` binary, over its stdio.
+
+#![cfg(feature = "lsp")]
+
+mod common;
+
+use std::io::{BufRead as _, BufReader, Read as _, Write as _};
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+use serde_json::{Value, json};
+
+use common::TempDir;
+
+struct Client {
+    child: Child,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_editorconfig-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        let mut client = Self { child, stdout, next_id: 0 };
+
+        client.request("initialize", json!({ "capabilities": {} }));
+        client.notify("initialized", json!({}));
+        client
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        let stdin = self.child.stdin.as_mut().unwrap();
+        write!(stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            self.stdout.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length: ") {
+                length = value.parse().unwrap();
+            }
+        }
+
+        let mut body = vec![0; length];
+        self.stdout.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    /// Sends a request, and returns its response (skipping notifications).
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(json!({
+            "jsonrpc": "2.0", "id": id, "method": method, "params": params,
+        }));
+
+        loop {
+            let message = self.receive();
+            if message["id"] == id {
+                return message;
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(
+            json!({ "jsonrpc": "2.0", "method": method, "params": params }),
+        );
+    }
+
+    fn open(&mut self, uri: &str, text: &str) {
+        self.notify(
+            "textDocument/didOpen",
+            json!({ "textDocument": {
+                "uri": uri, "languageId": "", "version": 1, "text": text,
+            } }),
+        );
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        self.child.wait().unwrap();
+    }
+}
+
+fn uri(path: &std::path::Path) -> String {
+    format!("file://{}", path.display())
+}
+
+#[test]
+fn answers_malformed_params_with_an_error() {
+    let mut client = Client::start();
+
+    let response = client.request("textDocument/hover", json!({ "x": 1 }));
+    assert_eq!(response["error"]["code"], -32602);
+
+    // A malformed notification is ignored too.
+    client.notify("textDocument/didOpen", json!([]));
+
+    // And the server keeps serving.
+    let response = client.request(
+        "textDocument/completion",
+        json!({
+            "textDocument": { "uri": "file:///a/.editorconfig" },
+            "position": { "line": 0, "character": 0 },
+        }),
+    );
+    assert_eq!(response["result"], json!([]));
+}
+
+#[test]
+fn goes_to_the_pair_which_sets_the_property_under_the_cursor() {
+    let dir = TempDir::new("lsp");
+    let ec_file = dir.write(
+        ".editorconfig",
+        "root = true\n\n[*]\nindent_style = tab\n\n[*.rs]\n  indent_size = 4\n",
+    );
+    let file = dir.write("main.rs", "// See indent_size.\nfn main() {}\n");
+
+    let mut client = Client::start();
+    client.open(&uri(&file), "// See indent_size.\nfn main() {}\n");
+
+    let definition = |client: &mut Client, character: u32| {
+        let response = client.request(
+            "textDocument/definition",
+            json!({
+                "textDocument": { "uri": uri(&file) },
+                "position": { "line": 0, "character": character },
+            }),
+        );
+        response["result"].clone()
+    };
+
+    assert_eq!(
+        definition(&mut client, 12),
+        json!({
+            "uri": uri(&ec_file),
+            "range": {
+                "start": { "line": 6, "character": 2 },
+                "end": { "line": 6, "character": 13 },
+            },
+        }),
+    );
+    // Not on the name of a property.
+    assert_eq!(definition(&mut client, 3), Value::Null);
+}
+
+#[test]
+fn lists_the_files_a_section_matches() {
+    let dir = TempDir::new("lsp-hover");
+    let text = "root = true\n\n[*.rs]\nindent_size = 4\n\n[*.txt]\n";
+    let ec_file = dir.write(".editorconfig", text);
+    dir.write(".gitignore", "target/\n");
+    dir.write("src/main.rs", "");
+    dir.write("target/build.rs", "");
+
+    let mut client = Client::start();
+    client.open(&uri(&ec_file), text);
+
+    let hover = |client: &mut Client, line: u32| {
+        let response = client.request(
+            "textDocument/hover",
+            json!({
+                "textDocument": { "uri": uri(&ec_file) },
+                "position": { "line": line, "character": 2 },
+            }),
+        );
+        response["result"]["contents"]["value"].clone()
+    };
+
+    assert_eq!(
+        hover(&mut client, 2),
+        json!("`[*.rs]` matches 1 file:\n\n- `src/main.rs`"),
+    );
+    assert_eq!(
+        hover(&mut client, 5),
+        json!("`[*.txt]` doesn't match any file"),
+    );
+}
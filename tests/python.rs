@@ -0,0 +1,104 @@
+//! Tests of the Python module, imported by an embedded interpreter.
+
+#![cfg(feature = "python")]
+
+mod common;
+
+use std::ffi::CString;
+use std::sync::Once;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+// Links the library, which exports the module's init function.
+use editorconfig_core as _;
+
+use common::TempDir;
+
+unsafe extern "C" {
+    fn PyInit_editorconfig_core() -> *mut pyo3::ffi::PyObject;
+}
+
+/// Runs the Python `code`, with the given (string) variables.
+fn run(code: &str, vars: &[(&str, &str)]) {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        // SAFETY: called before the interpreter is initialized.
+        unsafe {
+            pyo3::ffi::PyImport_AppendInittab(
+                c"editorconfig_core".as_ptr(),
+                Some(PyInit_editorconfig_core),
+            );
+        }
+        Python::initialize();
+    });
+
+    Python::attach(|py| {
+        let globals = PyDict::new(py);
+        for (name, value) in vars {
+            globals.set_item(name, value).unwrap();
+        }
+        let code = CString::new(code).unwrap();
+        if let Err(e) = py.run(&code, Some(&globals), None) {
+            e.display(py);
+            panic!("{e}");
+        }
+    });
+}
+
+#[test]
+fn gets_the_properties_as_a_dict() {
+    let dir = TempDir::new("python");
+    dir.write(".editorconfig", "root = true\n[*]\nindent_style = tab\n");
+    dir.write("ec", "root = true\n[*]\nindent_size = 2\n");
+    let file = dir.join("a.py");
+
+    run(
+        r#"
+import editorconfig_core as ec
+
+assert ec.get_properties(file) == {
+    "indent_style": "tab",
+    "indent_size": "tab",
+}, ec.get_properties(file)
+assert list(ec.get_properties(file)) == ["indent_size", "indent_style"]
+
+props = ec.get_properties(file, conf_filename="ec")
+assert props == {"indent_size": "2", "tab_width": "2"}, props
+
+# `indent_style = tab` doesn't imply `indent_size = tab` before 0.9.0.
+props = ec.get_properties(file, version="0.8.0")
+assert props == {"indent_style": "tab"}, props
+
+assert isinstance(ec.__version__, str)
+"#,
+        &[("file", file.to_str().unwrap())],
+    );
+}
+
+#[test]
+fn raises_editorconfig_errors() {
+    let dir = TempDir::new("python-errors");
+    dir.write(".editorconfig", "root = true\n[{3..1}]\nindent_size = 2\n");
+    let file = dir.join("a.py");
+
+    run(
+        r#"
+import editorconfig_core as ec
+
+def raises(error, *args, **kwargs):
+    try:
+        ec.get_properties(*args, **kwargs)
+    except error as e:
+        assert isinstance(e, ec.EditorConfigError)
+    else:
+        raise AssertionError(f"{error.__name__} not raised")
+
+raises(ec.PathError, "a.py")
+raises(ec.VersionError, "/a.py", version="x")
+raises(ec.VersionError, "/a.py", version="99.0.0")
+raises(ec.ParsingError, file)
+"#,
+        &[("file", file.to_str().unwrap())],
+    );
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "serde")]
+
+use editorconfig_core::{Document, Value, Version};
+use serde_json::json;
+
+#[test]
+fn serializes_versions_as_strings() {
+    let version = Version::new(0, 17, 2);
+    assert_eq!(serde_json::to_value(version).unwrap(), json!("0.17.2"));
+    assert_eq!(
+        serde_json::from_value::<Version>(json!("0.12.0")).unwrap(),
+        Version::new(0, 12, 0),
+    );
+    assert!(serde_json::from_value::<Version>(json!("0.x")).is_err());
+    assert!(serde_json::from_value::<Version>(json!([0, 12, 0])).is_err());
+}
+
+#[test]
+fn serializes_values_by_their_kind() {
+    let values = [
+        Value::Unset,
+        Value::Keyword("tab".to_owned()),
+        Value::Integer(4),
+        Value::Language("en-US".to_owned()),
+        Value::Other("x".to_owned()),
+    ];
+    let serialized = serde_json::to_value(&values).unwrap();
+    assert_eq!(
+        serialized,
+        json!([
+            "unset",
+            { "keyword": "tab" },
+            { "integer": 4 },
+            { "language": "en-US" },
+            { "other": "x" },
+        ]),
+    );
+    assert_eq!(
+        serde_json::from_value::<Vec<Value>>(serialized).unwrap(),
+        values,
+    );
+}
+
+#[test]
+fn round_trips_documents() {
+    let document = Document::parse("root = true\n\n[*.rs]\nindent_size = 4\n");
+    let serialized = serde_json::to_value(&document).unwrap();
+
+    assert_eq!(
+        serialized["lines"][2],
+        json!({
+            "number": 3,
+            "text": "[*.rs]",
+            "kind": { "section": { "pattern": { "start": 1, "end": 5 } } },
+        }),
+    );
+    assert_eq!(serialized["lines"][1]["kind"], json!("blank"));
+    assert_eq!(
+        serde_json::from_value::<Document>(serialized).unwrap(),
+        document,
+    );
+}
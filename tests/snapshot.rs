@@ -0,0 +1,73 @@
+mod common;
+
+use std::fs;
+
+use editorconfig_core::{Options, Snapshot, properties_with_options};
+
+use common::TempDir;
+
+#[test]
+fn resolves_like_parsing_and_notices_changes() {
+    let dir = TempDir::new("snapshot-changes");
+    dir.write(
+        ".editorconfig",
+        "root = true\n[*]\nindent_style = tab\n[{1..3}.rs]\nnum = yes\n",
+    );
+    dir.write("sub/.editorconfig", "[*.rs]\nindent_size = 2\n");
+
+    let snapshot = Snapshot::compile(dir.path(), Options::default()).unwrap();
+    let snapshot = Snapshot::from_bytes(&snapshot.to_bytes()).unwrap();
+    assert!(!snapshot.is_stale());
+
+    for file in ["a.rs", "2.rs", "sub/2.rs", "sub/b.md"] {
+        let path = dir.join(file);
+        assert_eq!(
+            snapshot.properties(&path, Options::default()).unwrap(),
+            properties_with_options(&path, Options::default()).unwrap(),
+            "{file}",
+        );
+    }
+
+    // The size changes too, so the change is noticed even if the
+    // modification time doesn't.
+    dir.write("sub/.editorconfig", "[*.rs]\nindent_size = 16\n");
+    assert!(snapshot.is_stale());
+    let properties =
+        snapshot.properties(dir.join("sub/a.rs"), Options::default()).unwrap();
+    assert_eq!(properties["indent_size"], "16");
+}
+
+#[test]
+fn rejects_other_buffers() {
+    assert!(Snapshot::from_bytes(b"").is_err());
+    assert!(Snapshot::from_bytes(b"ECSNAP\0\0\x02\0\0\0").is_err());
+
+    let dir = TempDir::new("snapshot-truncated");
+    dir.write(".editorconfig", "[*]\nkey = value\n");
+    let bytes =
+        Snapshot::compile(dir.path(), Options::default()).unwrap().to_bytes();
+    for len in 0..bytes.len() {
+        assert!(Snapshot::from_bytes(&bytes[..len]).is_err());
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn rejects_non_unicode_directories() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = TempDir::new("snapshot-non-unicode");
+    let sub = dir.join(OsStr::from_bytes(b"\xff"));
+    fs::create_dir_all(&sub).unwrap();
+    fs::write(sub.join(".editorconfig"), "[*]\nkey = value\n").unwrap();
+
+    assert!(matches!(
+        Snapshot::compile(dir.path(), Options::default()),
+        Err(editorconfig_core::Error::InvalidPath),
+    ));
+
+    // Without an EditorConfig file, the directory doesn't matter.
+    fs::remove_file(sub.join(".editorconfig")).unwrap();
+    assert!(Snapshot::compile(dir.path(), Options::default()).is_ok());
+}
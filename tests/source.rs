@@ -0,0 +1,76 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use editorconfig_core::{
+    ConfigSource, Error, Options, Version, properties_from_source,
+};
+
+fn configs() -> HashMap<PathBuf, String> {
+    HashMap::from([
+        (
+            PathBuf::from("/home/repo/.editorconfig"),
+            "root = true\n[*]\nindent_style = tab\n".to_owned(),
+        ),
+        (
+            PathBuf::from("/home/repo/src/.editorconfig"),
+            "[*.rs]\nindent_size = 4\n".to_owned(),
+        ),
+        // Above the root, so it's discarded.
+        (
+            PathBuf::from("/home/.editorconfig"),
+            "[*]\ncharset = latin1\n".to_owned(),
+        ),
+    ])
+}
+
+#[test]
+fn resolves_from_files_in_memory() {
+    let configs = configs();
+    let options = Options::default();
+
+    // As in the example of the wasm API.
+    let properties =
+        properties_from_source("/home/repo/README.md", options, &configs)
+            .unwrap();
+    assert_eq!(
+        properties,
+        HashMap::from([
+            ("indent_style".to_owned(), "tab".to_owned()),
+            ("indent_size".to_owned(), "tab".to_owned()),
+        ]),
+    );
+
+    let sorted: BTreeMap<_, _> = configs.into_iter().collect();
+    let properties =
+        properties_from_source("/home/repo/src/main.rs", options, &sorted)
+            .unwrap();
+    assert_eq!(properties["indent_size"], "4");
+    assert_eq!(properties["tab_width"], "4");
+
+    // The version applies as usual.
+    let options = options.with_version(Version::new(0, 8, 0));
+    let properties =
+        properties_from_source("/home/repo/README.md", options, &sorted)
+            .unwrap();
+    assert_eq!(properties.get("indent_size"), None);
+}
+
+/// A source which can't read anything.
+struct Failing;
+
+impl ConfigSource for Failing {
+    fn read(&self, _path: &Path) -> io::Result<Option<String>> {
+        Err(io::ErrorKind::PermissionDenied.into())
+    }
+}
+
+#[test]
+fn fails_with_the_errors_of_the_source() {
+    let result =
+        properties_from_source("/home/repo/a.rs", Options::default(), &Failing);
+    assert!(matches!(
+        result,
+        Err(Error::Io(e)) if e.kind() == io::ErrorKind::PermissionDenied
+    ));
+}
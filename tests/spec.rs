@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use editorconfig_core::{
+    Behavior, MAX_VERSION, Options, SPEC_CHANGES, Version,
+    properties_from_source,
+};
+
+#[test]
+fn lists_the_changes_in_order() {
+    let versions: Vec<_> = SPEC_CHANGES.iter().map(|c| c.version).collect();
+    assert_eq!(
+        versions,
+        [
+            Version::V0_9_0,
+            Version::V0_10_0,
+            Version::V0_12_0,
+            Version::V0_15_0,
+            Version::V0_15_0,
+        ],
+    );
+    assert!(versions.iter().all(Version::is_supported));
+    assert!(SPEC_CHANGES.iter().all(|c| !c.description.is_empty()));
+}
+
+#[test]
+fn applies_the_changes_up_to_the_version() {
+    let original = Behavior::of(Version::new(0, 8, 0));
+    assert_eq!(
+        original,
+        Behavior {
+            tab_indent_size: false,
+            numeric_ranges: false,
+            inline_comments: true,
+            colon_separators: true,
+            length_limits: false,
+        },
+    );
+
+    // Each change applies from its version on, and not before.
+    let behaviors = [
+        (
+            Version::new(0, 9, 0),
+            Behavior { tab_indent_size: true, ..original },
+        ),
+        (
+            Version::new(0, 11, 9),
+            Behavior {
+                tab_indent_size: true,
+                numeric_ranges: true,
+                ..original
+            },
+        ),
+        (
+            Version::new(0, 14, 99),
+            Behavior {
+                tab_indent_size: true,
+                numeric_ranges: true,
+                length_limits: true,
+                ..original
+            },
+        ),
+        (
+            Version::new(0, 15, 0),
+            Behavior {
+                tab_indent_size: true,
+                numeric_ranges: true,
+                inline_comments: false,
+                colon_separators: false,
+                length_limits: true,
+            },
+        ),
+    ];
+    for (version, behavior) in behaviors {
+        assert_eq!(Behavior::of(version), behavior, "{version}");
+    }
+
+    assert_eq!(Behavior::default(), Behavior::of(MAX_VERSION));
+    assert_eq!(Behavior::default(), Behavior::of(Version::new(0, 15, 0)));
+}
+
+#[test]
+fn does_not_gate_star_star_and_unset() {
+    let configs = HashMap::from([(
+        PathBuf::from("/home/repo/.editorconfig"),
+        "root = true\n[**.rs]\nindent_style = tab\ncharset = utf-8\n\
+         [src/**]\ncharset = unset\n"
+            .to_owned(),
+    )]);
+
+    for version in [Version::new(0, 8, 0), MAX_VERSION] {
+        let options = Options::default().with_version(version);
+        let properties =
+            properties_from_source("/home/repo/src/a/b.rs", options, &configs)
+                .unwrap();
+        assert_eq!(properties["indent_style"], "tab", "{version}");
+        assert_eq!(properties["charset"], "unset", "{version}");
+    }
+}
@@ -0,0 +1,181 @@
+mod common;
+
+use std::fs;
+use std::num::NonZeroUsize;
+
+use editorconfig_core::{
+    TreeOptions, files_with_property, properties, resolve_tree,
+};
+
+use common::TempDir;
+
+fn tree() -> TempDir {
+    let dir = TempDir::new("tree");
+    dir.write(
+        ".editorconfig",
+        "root = true\n[*]\nindent_style = space\n[*.md]\nindent_size = 2\n",
+    );
+    dir.write(
+        "src/.editorconfig",
+        "[*.rs]\nindent_size = 4\n[lib.rs]\ncharset = latin1\n",
+    );
+    dir.write(".gitignore", "target/\n");
+    dir.write(".git/HEAD", "");
+    dir.write("target/debug/main", "");
+    dir.write("README.md", "");
+    dir.write("src/lib.rs", "");
+    // Enough files to be resolved in parallel.
+    for idx in 0..300 {
+        dir.write(format!("src/{}/{idx}.rs", idx % 10), "");
+    }
+    dir
+}
+
+#[test]
+fn resolves_every_file_like_properties() {
+    let dir = tree();
+
+    for threads in [1, 4] {
+        let options =
+            TreeOptions::default().with_threads(NonZeroUsize::new(threads));
+        let entries = resolve_tree(dir.path(), options).unwrap();
+
+        let paths: Vec<_> = entries.iter().map(|(path, _)| path).collect();
+        let mut sorted = paths.clone();
+        sorted.sort();
+        assert_eq!(paths, sorted);
+        // `.git` is always skipped, but the ignored files aren't by default.
+        assert_eq!(entries.len(), 306);
+        assert!(!paths.iter().any(|p| p.starts_with(dir.join(".git"))));
+        assert!(paths.contains(&&dir.join("target/debug/main")));
+
+        for (path, resolved) in entries.iter() {
+            assert_eq!(resolved.as_ref().unwrap(), &properties(path).unwrap());
+        }
+    }
+
+    let entries = resolve_tree(dir.path(), TreeOptions::default()).unwrap();
+    let lib = entries.iter().find(|(p, _)| p == &dir.join("src/lib.rs"));
+    let lib = lib.unwrap().1.as_ref().unwrap();
+    assert_eq!(lib["charset"], "latin1");
+    assert_eq!(lib["indent_size"], "4");
+}
+
+#[cfg(feature = "ignore")]
+#[test]
+fn skips_the_ignored_files_if_asked_to() {
+    let dir = tree();
+    let options = TreeOptions::default().with_ignore_files(true);
+    let entries = resolve_tree(dir.path(), options).unwrap();
+
+    assert_eq!(entries.len(), 305);
+    assert!(!entries.iter().any(|(p, _)| p.starts_with(dir.join("target"))));
+}
+
+#[test]
+fn reports_errors_per_file() {
+    let dir = TempDir::new("tree-errors");
+    dir.write(".editorconfig", "root = true\n[*.rs]\nindent_size = 4\n");
+    dir.write("sub/.editorconfig", "[{3..1}.rs]\nindent_size = 2\n");
+    dir.write("a.rs", "");
+    dir.write("sub/b.rs", "");
+
+    let entries = resolve_tree(dir.path(), TreeOptions::default()).unwrap();
+    let (ok, failed): (Vec<_>, Vec<_>) =
+        entries.iter().partition(|(_path, resolved)| resolved.is_ok());
+
+    assert!(ok.iter().any(|(p, _)| p == &dir.join("a.rs")));
+    assert_eq!(failed.len(), 2);
+    assert!(failed.iter().all(|(p, _)| p.starts_with(dir.join("sub"))));
+}
+
+#[test]
+fn keeps_a_bad_ec_file_to_the_files_under_it() {
+    let dir = TempDir::new("tree-bad-file");
+    dir.write(".editorconfig", "root = true\n[*]\nindent_size = 4\n");
+    // Not UTF-8, so it can't be read.
+    let bad = dir.write("bad/.editorconfig", "");
+    fs::write(bad, b"[*]\nlabel = \xff\n").unwrap();
+    dir.write("bad/a.rs", "");
+    dir.write("bad/nested/b.rs", "");
+    dir.write("good/.editorconfig", "[*]\nindent_size = 2\n");
+    dir.write("good/c.rs", "");
+    dir.write("d.rs", "");
+
+    let entries = resolve_tree(dir.path(), TreeOptions::default()).unwrap();
+    assert_eq!(entries.len(), 7);
+    for (path, resolved) in &entries {
+        if path.starts_with(dir.join("bad")) {
+            assert!(resolved.is_err(), "{}", path.display());
+        } else {
+            assert!(resolved.is_ok(), "{}", path.display());
+        }
+    }
+    let good = entries.iter().find(|(p, _)| p == &dir.join("good/c.rs"));
+    assert_eq!(good.unwrap().1.as_ref().unwrap()["indent_size"], "2");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn skips_the_directories_which_cant_be_read() {
+    let dir = TempDir::new("tree-unreadable");
+    dir.write(".editorconfig", "root = true\n[*]\nindent_size = 4\n");
+    dir.write("a.rs", "");
+    dir.unreadable_dir("deep");
+
+    let entries = resolve_tree(dir.path(), TreeOptions::default()).unwrap();
+    assert!(entries.iter().all(|(_path, resolved)| resolved.is_ok()));
+    assert!(entries.iter().any(|(p, _)| p == &dir.join("a.rs")));
+}
+
+#[test]
+fn finds_the_files_with_a_property() {
+    let dir = TempDir::new("tree-where");
+    dir.write(
+        ".editorconfig",
+        "root = true\n[*]\nIndent_Style = Tab\n[*.md]\nmax_line_length = 80\n",
+    );
+    // Set, and then unset in a nested section.
+    dir.write(
+        "docs/.editorconfig",
+        "[*.md]\nmax_line_length = 100\n[old/*.md]\nmax_line_length = unset\n",
+    );
+    dir.write("README.md", "");
+    dir.write("src/main.rs", "");
+    dir.write("docs/guide.md", "");
+    dir.write("docs/old/notes.md", "");
+
+    let find = |key, value| {
+        let entries =
+            files_with_property(dir.path(), key, value, TreeOptions::default())
+                .unwrap();
+        let mut paths: Vec<_> = entries
+            .into_iter()
+            .map(|(path, resolved)| {
+                assert!(resolved.is_ok());
+                path.strip_prefix(dir.path()).unwrap().to_owned()
+            })
+            .collect();
+        paths.sort();
+        paths
+    };
+    let paths = |paths: &[&str]| -> Vec<_> {
+        paths.iter().map(std::path::PathBuf::from).collect()
+    };
+
+    assert_eq!(
+        find("max_line_length", None),
+        paths(&["README.md", "docs/guide.md"]),
+    );
+    assert_eq!(
+        find("max_line_length", Some("100")),
+        paths(&["docs/guide.md"])
+    );
+    assert_eq!(
+        find("max_line_length", Some("unset")),
+        paths(&["docs/old/notes.md"]),
+    );
+    // Normalized like the EditorConfig files.
+    assert_eq!(find("INDENT_STYLE", Some("TAB")).len(), 6);
+    assert_eq!(find("charset", None), paths(&[]));
+}
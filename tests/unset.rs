@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use editorconfig_core::{Options, Properties, Version, properties_from_source};
+
+fn resolve(nested: &str, options: Options) -> Properties {
+    let configs = HashMap::from([
+        (
+            PathBuf::from("/home/repo/.editorconfig"),
+            "root = true\n[*]\nindent_style = space\nindent_size = 2\n\
+             charset = utf-8\nlabel = x\n"
+                .to_owned(),
+        ),
+        (
+            PathBuf::from("/home/repo/src/.editorconfig"),
+            nested.to_owned(),
+        ),
+    ]);
+    properties_from_source("/home/repo/src/a.rs", options, &configs).unwrap()
+}
+
+#[test]
+fn keeps_the_unset_properties_by_default() {
+    let properties = resolve(
+        "[*]\ncharset = unset\nlabel = UNSET\nunset = 1\n",
+        Options::default(),
+    );
+
+    assert_eq!(properties["charset"], "unset");
+    assert_eq!(properties["label"], "UNSET");
+    // A property named `unset` is always dropped.
+    assert_eq!(properties.get("unset"), None);
+    assert_eq!(properties["indent_style"], "space");
+}
+
+#[test]
+fn removes_the_unset_properties_if_asked_to() {
+    let options = Options::default().with_remove_unset(true);
+    let properties =
+        resolve("[*]\ncharset = unset\nlabel = UNSET\nunset = 1\n", options);
+
+    assert_eq!(properties.get("charset"), None);
+    // Whatever the case, even though the value isn't lowercased.
+    assert_eq!(properties.get("label"), None);
+    assert_eq!(properties.get("unset"), None);
+    assert_eq!(properties["indent_style"], "space");
+    assert_eq!(properties["indent_size"], "2");
+}
+
+#[test]
+fn doesnt_derive_values_from_unset_properties() {
+    for remove_unset in [false, true] {
+        let options = Options::default().with_remove_unset(remove_unset);
+
+        // `tab_width` isn't copied from an unset `indent_size`.
+        let properties = resolve("[*.rs]\nindent_size = unset\n", options);
+        assert_eq!(properties.get("tab_width"), None);
+        assert_eq!(
+            properties.get("indent_size").map(String::as_str),
+            (!remove_unset).then_some("unset"),
+        );
+
+        // Nor is `indent_size` copied from an unset `tab_width`.
+        let properties = resolve(
+            "[*.rs]\nindent_style = tab\nindent_size = tab\ntab_width = unset\n",
+            options,
+        );
+        assert_eq!(properties["indent_size"], "tab");
+        assert_eq!(
+            properties.get("tab_width").map(String::as_str),
+            (!remove_unset).then_some("unset"),
+        );
+    }
+}
+
+#[test]
+fn builds_the_options() {
+    let options = Options::default()
+        .with_file_name(".ec")
+        .with_version(Version::V0_12_0)
+        .with_remove_unset(true);
+
+    assert_eq!(options.file_name, ".ec");
+    assert_eq!(options.version, Version::V0_12_0);
+    assert!(options.remove_unset);
+    assert!(!Options::default().remove_unset);
+}
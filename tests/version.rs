@@ -0,0 +1,47 @@
+use editorconfig_core::{MAX_VERSION, ParseVersionError, Version};
+
+#[test]
+fn parses_partial_and_suffixed_versions() {
+    let parses = [
+        ("0.12.1", Version::new(0, 12, 1)),
+        ("0.12", Version::new(0, 12, 0)),
+        ("1", Version::new(1, 0, 0)),
+        ("1.0.0-beta", Version::new(1, 0, 0)),
+        ("0.17.2+build.5", Version::new(0, 17, 2)),
+        ("007.08.09", Version::new(7, 8, 9)),
+    ];
+    for (s, version) in parses {
+        assert_eq!(s.parse(), Ok(version), "{s}");
+    }
+}
+
+#[test]
+fn rejects_invalid_versions() {
+    let errors = [
+        ("", ParseVersionError::Empty),
+        ("-beta", ParseVersionError::Empty),
+        ("1.2.3.4", ParseVersionError::TooManySegments),
+        ("1..2", ParseVersionError::InvalidSegment),
+        ("1.2.", ParseVersionError::InvalidSegment),
+        ("+1.2", ParseVersionError::Empty),
+        ("1.+2", ParseVersionError::InvalidSegment),
+        ("v1.2", ParseVersionError::InvalidSegment),
+        (" 1.2", ParseVersionError::InvalidSegment),
+        ("1.99999999999", ParseVersionError::InvalidSegment),
+    ];
+    for (s, error) in errors {
+        assert_eq!(s.parse::<Version>(), Err(error), "{s:?}");
+    }
+}
+
+#[test]
+fn orders_and_displays_versions() {
+    assert!(Version::new(0, 9, 10) < Version::new(0, 10, 0));
+    assert!(Version::new(1, 0, 0) > Version::new(0, 99, 99));
+    assert_eq!(Version::new(0, 12, 0).to_string(), "0.12.0");
+
+    assert!(MAX_VERSION.is_supported());
+    assert!(Version::new(0, 0, 0).is_supported());
+    let next = Version::new(MAX_VERSION.major, MAX_VERSION.minor + 1, 0);
+    assert!(!next.is_supported());
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "watch")]
+
+mod common;
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use editorconfig_core::{Options, PropertiesChange, Watcher};
+
+use common::TempDir;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[test]
+fn reloads_the_properties_when_an_ec_file_changes() {
+    let dir = TempDir::new("watch");
+    let ec_file =
+        dir.write(".editorconfig", "root = true\n[*]\nindent_size = 2\n");
+    let file = dir.join("src/main.rs");
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = Watcher::new(Options::default(), move |event| {
+        let _ = sender.send(event);
+    })
+    .unwrap();
+
+    assert_eq!(watcher.watch(&file).unwrap()["indent_size"], "2");
+
+    std::fs::write(&ec_file, "root = true\n[*]\nindent_size = 4\n").unwrap();
+
+    // The file may be seen truncated first, so there may be an event in
+    // between.
+    let mut changes = vec![];
+    while changes.last().is_none_or(|c: &PropertiesChange| {
+        c.after.get("indent_size").is_none_or(|v| v != "4")
+    }) {
+        let event = receiver.recv_timeout(TIMEOUT).unwrap();
+        assert!(event.ec_files.contains(&ec_file));
+        assert!(event.changes.iter().all(|c| c.path == file));
+        changes.extend(event.changes);
+    }
+    assert_eq!(changes[0].before["indent_size"], "2");
+
+    assert_eq!(watcher.properties(&file).unwrap()["indent_size"], "4");
+
+    // Once unwatched, the path has no properties anymore.
+    watcher.unwatch(&file);
+    assert_eq!(watcher.properties(&file), None);
+}
+
+#[test]
+fn watches_while_ec_files_change() {
+    let dir = TempDir::new("watch-busy");
+    let ec_file = dir.write(".editorconfig", "root = true\n");
+
+    let mut watcher = Watcher::new(Options::default(), |_event| {
+        // Hold the event thread, so that it's busy while paths are watched.
+        thread::sleep(Duration::from_millis(1));
+    })
+    .unwrap();
+    watcher.watch(dir.join("a.rs")).unwrap();
+
+    let writer = thread::spawn(move || {
+        for size in 0..200 {
+            let contents = format!("root = true\n[*]\nindent_size = {size}\n");
+            std::fs::write(&ec_file, contents).unwrap();
+        }
+    });
+
+    // Watching and unwatching must not deadlock with the handler.
+    let (done, finished) = mpsc::channel();
+    let paths: Vec<_> = (0..200)
+        .map(|idx| dir.join(format!("sub{}/{idx}.rs", idx % 7)))
+        .collect();
+    thread::spawn(move || {
+        for path in paths.iter() {
+            watcher.watch(path).unwrap();
+        }
+        for path in paths.iter() {
+            watcher.unwatch(path);
+        }
+        let _ = done.send(());
+    });
+
+    finished.recv_timeout(TIMEOUT).expect("watching deadlocked");
+    writer.join().unwrap();
+}
+
+#[test]
+fn reloads_an_ec_file_once_its_fixed() {
+    let dir = TempDir::new("watch-broken");
+    let ec_file =
+        dir.write(".editorconfig", "root = true\n[*]\nindent_size = 2\n");
+    let file = dir.join("main.rs");
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = Watcher::new(Options::default(), move |event| {
+        let _ = sender.send(event);
+    })
+    .unwrap();
+    assert_eq!(watcher.watch(&file).unwrap()["indent_size"], "2");
+
+    // Not valid UTF-8, so it can't be read.
+    std::fs::write(&ec_file, b"root = true\n[*]\nindent_size = \xff\n")
+        .unwrap();
+    loop {
+        let event = receiver.recv_timeout(TIMEOUT).unwrap();
+        if !event.errors.is_empty() {
+            break;
+        }
+    }
+
+    std::fs::write(&ec_file, "root = true\n[*]\nindent_size = 4\n").unwrap();
+    loop {
+        let event = receiver.recv_timeout(TIMEOUT).unwrap();
+        let change = event.changes.iter().find(|c| c.path == file);
+        // The file may be seen truncated first, as in the test above.
+        if change.is_some_and(|c| {
+            c.after.get("indent_size").is_some_and(|v| v == "4")
+        }) {
+            break;
+        }
+    }
+    assert_eq!(watcher.properties(&file).unwrap()["indent_size"], "4");
+}